@@ -1,40 +1,80 @@
-use crate::crypto::verify_arbitrary;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SignedData};
-use crate::state::{Campaign, ADMIN, CAMPAIGN_POOL, NONCES, PUBKEY};
+use crate::crypto::{
+    active_signers, address_prefix, pubkey_to_account, signed_document, signing_domain,
+    validate_hrp, validate_pubkey, validate_signer_key, verify_arbitrary, verify_merkle_proof,
+};
+use crate::msg::{
+    AdminResponse, ApprovalPolicyResponse, ApprovalThresholdResponse, CampaignResponse, ClaimItem,
+    ClaimedResponse, DenomResponse, DenomSolvencyResponse, ExecuteMsg, InstantiateMsg,
+    ListDenomsResponse, ListRolesResponse, MerkleClaimedResponse, NonceResponse, NoncesResponse,
+    QueryMsg, ReceiveMsg, RoleAssignmentResponse, SignedCumulativeData, SignedData,
+    SignerKeyResponse, SignerKeysResponse, SigningDomainResponse, SolvencyResponse,
+};
+use crate::state::{
+    AllowedDenom, Campaign, CampaignStatus, GraceKey, KeyType, NonceStatus, PendingAdmin, Role,
+    SignerKey, ADMIN, ALLOCATED, ALLOWED_DENOMS, APPROVAL_THRESHOLDS, CAMPAIGN_POOL, CLAIMED,
+    CLOSED_CAMPAIGNS, HRP, LEGACY_CAMPAIGN_POOL, LEGACY_NONCES, LEGACY_SIGNATURES, MERKLE_CLAIMED,
+    NONCES, PENDING_ADMIN, PREVIOUS_PUBKEY, PUBKEY, REMOVED_CAMPAIGNS, REQUIRED_SIGNATURES,
+    REVOKED_NONCES, ROLES, SIGNERS,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Response, StdError, StdResult, Uint128,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    Event, HexBinary, MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp,
+    Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 use semver::Version;
+use std::collections::BTreeMap;
 
 const CONTRACT_NAME: &str = "crates.io:reward_pool";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_PRUNE_LIMIT: u32 = 100;
+const MAX_PRUNE_LIMIT: u32 = 500;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, StdError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     PUBKEY.save(deps.storage, &msg.pubkey)?;
+    let hrp = match msg.hrp {
+        Some(hrp) => hrp,
+        None => address_prefix(env.contract.address.as_str())?,
+    };
+    validate_hrp(&hrp)?;
+    HRP.save(deps.storage, &hrp)?;
+    // new deployments only accept signatures bound to their signing domain
+    LEGACY_SIGNATURES.save(deps.storage, &false)?;
     ADMIN.save(
         deps.storage,
         &(deps.api.addr_canonicalize(info.sender.as_str()))?,
     )?;
 
+    // the staking denom is accepted out of the box, anything else is added by the admin
+    ALLOWED_DENOMS.save(
+        deps.storage,
+        &deps.querier.query_bonded_denom()?,
+        &AllowedDenom { min_deposit: None },
+    )?;
+
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, StdError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, StdError> {
     let new_version: Version = CONTRACT_VERSION.parse().unwrap();
     let current_version = get_contract_version(deps.storage)?;
 
@@ -44,7 +84,8 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, StdErr
         ));
     }
 
-    if current_version.version.parse::<Version>().unwrap() >= new_version {
+    let stored_version: Version = current_version.version.parse().unwrap();
+    if stored_version >= new_version {
         return Err(StdError::generic_err(
             "Cannot upgrade from a newer contract version",
         ));
@@ -52,6 +93,75 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, StdErr
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // contracts deployed before the allow-list existed only accepted the staking denom
+    if ALLOWED_DENOMS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
+    {
+        ALLOWED_DENOMS.save(
+            deps.storage,
+            &deps.querier.query_bonded_denom()?,
+            &AllowedDenom { min_deposit: None },
+        )?;
+    }
+
+    if !HRP.exists(deps.storage) {
+        HRP.save(
+            deps.storage,
+            &address_prefix(env.contract.address.as_str())?,
+        )?;
+    }
+
+    if stored_version < Version::new(0, 2, 0) {
+        // campaigns of the 0.1.2 release did not record their denom, they all hold the staking denom
+        let native_denom = deps.querier.query_bonded_denom()?;
+        let campaigns = LEGACY_CAMPAIGN_POOL
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (campaign_id, campaign) in campaigns {
+            if campaign.denom.is_none() {
+                CAMPAIGN_POOL.save(
+                    deps.storage,
+                    campaign_id,
+                    &Campaign {
+                        amount: campaign.amount,
+                        owner: campaign.owner,
+                        denom: Denom::Native(native_denom.clone()),
+                        start_time: None,
+                        end_time: None,
+                        merkle_root: None,
+                        signer: None,
+                    },
+                )?;
+            }
+        }
+
+        // the 0.1.2 release tracked nonces as global strings, which have no campaign and claimer
+        // to be moved under, so its vouchers can no longer be claimed and have to be reissued.
+        // The list is left for prune_nonces to delete in pages.
+    }
+
+    // contracts deployed before withdrawals were limited to the surplus did not track allocations
+    if ALLOCATED
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
+    {
+        let campaigns = CAMPAIGN_POOL
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, campaign)| campaign))
+            .collect::<StdResult<Vec<_>>>()?;
+        for campaign in campaigns {
+            allocate(deps.storage, &denom_id(&campaign.denom), campaign.amount)?;
+        }
+    }
+
+    // backends that do not sign the domain yet keep working until the admin disables them
+    if !LEGACY_SIGNATURES.exists(deps.storage) {
+        LEGACY_SIGNATURES.save(deps.storage, &true)?;
+    }
+
     Ok(Response::new().add_attribute("method", "migrate"))
 }
 
@@ -63,14 +173,41 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, StdError> {
     match msg {
-        ExecuteMsg::EditAdmin { new_admin } => edit_admin(deps, info, new_admin),
-        ExecuteMsg::Deposit { campaign_id } => deposit(deps, env, info, campaign_id),
+        ExecuteMsg::ProposeAdmin {
+            new_admin,
+            expires_at,
+        } => propose_admin(deps, env, info, new_admin, expires_at),
+        ExecuteMsg::GrantRole { address, role } => grant_role(deps, env, info, address, role),
+        ExecuteMsg::RevokeRole { address, role } => revoke_role(deps, env, info, address, role),
+        ExecuteMsg::AcceptAdmin {} => accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => cancel_admin_proposal(deps, env, info),
+        ExecuteMsg::Deposit {
+            campaign_id,
+            start_time,
+            end_time,
+            signer,
+            signer_type,
+        } => deposit(
+            deps,
+            env,
+            info,
+            campaign_id,
+            start_time,
+            end_time,
+            signer.map(|key| SignerKey {
+                key_type: signer_type.unwrap_or_default(),
+                key,
+            }),
+        ),
         ExecuteMsg::Claim {
             campaign_id,
             amount,
             denom,
             nonce,
+            recipient,
             signature,
+            cosignatures,
+            expires_at,
         } => claim(
             deps,
             env,
@@ -79,261 +216,2037 @@ pub fn execute(
             denom,
             amount,
             nonce,
+            recipient,
             signature,
+            cosignatures,
+            expires_at,
         ),
-        ExecuteMsg::Withdraw { amount } => withdraw(deps, env, info, amount),
+        ExecuteMsg::ClaimFor {
+            campaign_id,
+            amount,
+            denom,
+            nonce,
+            recipient,
+            relayer_fee,
+            signature,
+            cosignatures,
+            expires_at,
+        } => claim_for(
+            deps,
+            env,
+            info,
+            campaign_id,
+            denom,
+            amount,
+            nonce,
+            recipient,
+            relayer_fee,
+            signature,
+            cosignatures,
+            expires_at,
+        ),
+        ExecuteMsg::ClaimBatch { claims } => claim_batch(deps, env, info, claims),
+        ExecuteMsg::ClaimCumulative {
+            campaign_id,
+            denom,
+            total,
+            signature,
+            cosignatures,
+            expires_at,
+        } => claim_cumulative(
+            deps,
+            env,
+            info,
+            campaign_id,
+            denom,
+            total,
+            signature,
+            cosignatures,
+            expires_at,
+        ),
+        ExecuteMsg::RegisterMerkleRoot {
+            campaign_id,
+            merkle_root,
+        } => register_merkle_root(deps, env, info, campaign_id, merkle_root),
+        ExecuteMsg::ClaimMerkle {
+            campaign_id,
+            amount,
+            index,
+            proof,
+        } => claim_merkle(deps, env, info, campaign_id, amount, index, proof),
+        ExecuteMsg::Withdraw {
+            amount,
+            denom,
+            token,
+        } => withdraw(deps, env, info, amount, denom, token),
+        ExecuteMsg::EmergencyWithdraw {
+            amount,
+            denom,
+            token,
+            reason,
+        } => emergency_withdraw(deps, env, info, amount, denom, token, reason),
         ExecuteMsg::Cancel { campaign_id } => cancel(deps, env, info, campaign_id),
         ExecuteMsg::SetCpool {
             campaign_id,
             amount,
         } => set_cpool(deps, env, info, campaign_id, amount),
+        ExecuteMsg::Receive(wrapper) => receive(deps, env, info, wrapper),
+        ExecuteMsg::AddDenom { denom, min_deposit } => {
+            add_denom(deps, env, info, denom, min_deposit)
+        }
+        ExecuteMsg::RemoveDenom { denom } => remove_denom(deps, env, info, denom),
+        ExecuteMsg::SweepExpired { campaign_ids } => sweep_expired(deps, env, info, campaign_ids),
+        ExecuteMsg::SetLegacySignatures { enabled } => {
+            set_legacy_signatures(deps, env, info, enabled)
+        }
+        ExecuteMsg::SetHrp { hrp } => set_hrp(deps, env, info, hrp),
+        ExecuteMsg::RotateSigner {
+            pubkey,
+            grace_period,
+        } => rotate_signer(deps, env, info, pubkey, grace_period),
+        ExecuteMsg::AddSigner { pubkey, key_type } => {
+            add_signer(deps, env, info, pubkey, key_type.unwrap_or_default())
+        }
+        ExecuteMsg::RemoveSigner { pubkey } => remove_signer(deps, env, info, pubkey),
+        ExecuteMsg::SetRequiredSignatures { required } => {
+            set_required_signatures(deps, env, info, required)
+        }
+        ExecuteMsg::RevokeNonces {
+            campaign_id,
+            sender,
+            nonces,
+        } => revoke_nonces(deps, env, info, campaign_id, sender, nonces),
+        ExecuteMsg::PruneNonces { campaign_id, limit } => {
+            prune_nonces(deps, env, info, campaign_id, limit)
+        }
+        ExecuteMsg::SetApprovalThreshold { denom, threshold } => {
+            set_approval_threshold(deps, env, info, denom, threshold)
+        }
     }
 }
 
-pub fn edit_admin(deps: DepsMut, info: MessageInfo, new_admin: Addr) -> Result<Response, StdError> {
+/// Starts the transfer of the admin role, replacing any pending proposal
+pub fn propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires_at: Option<Expiration>,
+) -> Result<Response, StdError> {
     let admin = ADMIN.load(deps.storage)?;
-    if admin != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("This function is for Admin only"));
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can transfer the admin role",
+        ));
+    }
+
+    if expires_at.is_some_and(|expires_at| expires_at.is_expired(&env.block)) {
+        return Err(StdError::generic_err(
+            "Proposal expiry must be in the future",
+        ));
+    }
+
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            address: new_admin.clone(),
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "propose_admin")
+        .add_attribute("pending_admin", new_admin))
+}
+
+pub fn accept_admin(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, StdError> {
+    let pending = match PENDING_ADMIN.may_load(deps.storage)? {
+        Some(pending) => pending,
+        None => return Err(StdError::generic_err("No admin transfer is pending")),
+    };
+
+    if info.sender != pending.address {
+        return Err(StdError::generic_err(
+            "Only the proposed admin can accept the admin role",
+        ));
+    }
+
+    if pending
+        .expires_at
+        .is_some_and(|expires_at| expires_at.is_expired(&env.block))
+    {
+        return Err(StdError::generic_err("Admin proposal has expired"));
     }
 
     ADMIN.save(
         deps.storage,
-        &deps.api.addr_canonicalize(new_admin.as_str())?,
+        &deps.api.addr_canonicalize(info.sender.as_str())?,
     )?;
+    PENDING_ADMIN.remove(deps.storage);
 
-    Ok(Response::new())
+    Ok(Response::new()
+        .add_attribute("method", "accept_admin")
+        .add_attribute("admin", info.sender))
 }
 
-pub fn deposit(
+pub fn cancel_admin_proposal(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    campaign_id: String,
 ) -> Result<Response, StdError> {
-    let native_denom = deps.querier.query_bonded_denom()?;
-    let mut funds = info.funds.clone();
-    let coin = funds.pop();
+    let admin = ADMIN.load(deps.storage)?;
 
-    if funds.len() > 1 {
-        return Err(StdError::generic_err("Only one coin is allowed"));
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can transfer the admin role",
+        ));
     }
 
-    let amount_sent = match coin {
-        Some(ref coin) => coin.amount,
-        None => return Err(StdError::generic_err("No funds were sent")),
-    };
-
-    if coin.unwrap().denom != native_denom {
-        return Err(StdError::generic_err("Invalid denom"));
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(StdError::generic_err("No admin transfer is pending"));
     }
+    PENDING_ADMIN.remove(deps.storage);
 
-    match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
-        Some(mut campaign) => {
-            campaign.amount += amount_sent;
-            CAMPAIGN_POOL.save(deps.storage, campaign_id.clone(), &campaign)?;
-        }
-        None => CAMPAIGN_POOL.save(
-            deps.storage,
-            campaign_id.clone(),
-            &Campaign {
-                owner: info.sender,
-                amount: amount_sent,
-            },
-        )?,
-    };
+    Ok(Response::new().add_attribute("method", "cancel_admin_proposal"))
+}
+
+/// Whether `sender` is the admin, who holds every role, or has been granted `role`
+fn has_role(deps: Deps, sender: &Addr, role: Role) -> StdResult<bool> {
+    if deps.api.addr_canonicalize(sender.as_str())? == ADMIN.load(deps.storage)? {
+        return Ok(true);
+    }
 
-    Ok(Response::new().add_attribute("method", "deposit"))
+    Ok(ROLES
+        .may_load(deps.storage, sender)?
+        .is_some_and(|roles| roles.contains(&role)))
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn claim(
+pub fn grant_role(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    campaign_id: String,
-    denom: String,
-    amount: Uint128,
-    nonce: String,
-    signature: Binary,
+    address: String,
+    role: Role,
 ) -> Result<Response, StdError> {
-    // Check if nonce has been used
-    if NONCES.has(deps.storage, &nonce) {
-        return Err(StdError::generic_err("Nonce has been used"));
-    } else {
-        NONCES.save(deps.storage, &nonce, &true)?;
-    }
-
-    // Check if data is correctly signed
-    verify_arbitrary(
-        deps.as_ref(),
-        &SignedData {
-            campaign_id: campaign_id.clone(),
-            amount,
-            denom: denom.clone(),
-            nonce: nonce.clone(),
-            sender: info.sender.clone(),
-        },
-        &signature,
-    )?;
+    let admin = ADMIN.load(deps.storage)?;
 
-    // Check if campaign exists and has funds
-    if let Some(data) = CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
-        let mut campaign = data;
-        if campaign.amount > amount {
-            campaign.amount -= amount;
-            CAMPAIGN_POOL.save(deps.storage, campaign_id, &campaign)?;
-        } else {
-            return Err(StdError::generic_err("Campaign does not have enough funds"));
-        }
-    } else {
-        return Err(StdError::generic_err("Campaign ID not valid"));
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can manage roles",
+        ));
     }
 
-    // Check if denom requested is used by contract
-    let native_denom = deps.querier.query_bonded_denom()?;
-    if native_denom != denom {
-        return Err(StdError::generic_err("Invalid denom"));
+    let address = deps.api.addr_validate(&address)?;
+    let mut roles = ROLES.may_load(deps.storage, &address)?.unwrap_or_default();
+    if roles.contains(&role) {
+        return Err(StdError::generic_err("Address already has this role"));
     }
+    roles.push(role.clone());
+    ROLES.save(deps.storage, &address, &roles)?;
 
-    // Send funds
     Ok(Response::new()
-        .add_attribute("method", "claim")
-        .add_message(CosmosMsg::Bank(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![Coin {
-                denom: denom.clone(),
-                amount,
-            }],
-        })))
+        .add_attribute("method", "grant_role")
+        .add_attribute("address", address)
+        .add_attribute("role", role.to_string()))
 }
 
-pub fn withdraw(
+pub fn revoke_role(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
-    amount: Uint128,
+    address: String,
+    role: Role,
 ) -> Result<Response, StdError> {
     let admin = ADMIN.load(deps.storage)?;
 
     if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
-        return Err(StdError::generic_err("Only contract owner can withdraw"));
+        return Err(StdError::generic_err(
+            "Only contract owner can manage roles",
+        ));
     }
 
-    let native_denom = deps.querier.query_bonded_denom()?;
-
-    let own_balance: Uint128 = deps
-        .querier
-        .query_balance(env.contract.address, native_denom.clone())
-        .unwrap_or_default()
-        .amount;
-
-    if amount > own_balance {
-        return Err(StdError::generic_err("Not enough funds in the contract"));
+    let address = deps.api.addr_validate(&address)?;
+    let mut roles = ROLES.may_load(deps.storage, &address)?.unwrap_or_default();
+    if !roles.contains(&role) {
+        return Err(StdError::generic_err("Address does not have this role"));
+    }
+    roles.retain(|granted| granted != &role);
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, &address);
+    } else {
+        ROLES.save(deps.storage, &address, &roles)?;
     }
-    let to_address = deps.api.addr_humanize(&admin)?.to_string();
-
-    let res = Response::new()
-        .add_attribute("method", "withdraw")
-        .add_message(CosmosMsg::Bank(BankMsg::Send {
-            to_address,
-            amount: vec![Coin {
-                denom: native_denom.clone(),
-                amount,
-            }],
-        }));
 
-    Ok(res)
+    Ok(Response::new()
+        .add_attribute("method", "revoke_role")
+        .add_attribute("address", address)
+        .add_attribute("role", role.to_string()))
 }
 
-pub fn cancel(
+pub fn deposit(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     campaign_id: String,
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+    signer: Option<SignerKey>,
 ) -> Result<Response, StdError> {
-    let admin = ADMIN.load(deps.storage)?;
+    if info.funds.len() > 1 {
+        return Err(StdError::generic_err("Only one coin is allowed"));
+    }
 
-    match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
-        Some(campaign) => {
-            if deps.api.addr_canonicalize(info.sender.as_str())? != admin
-                && info.sender != campaign.owner
-            {
-                return Err(StdError::generic_err(
-                    "Only campaign owner can cancel the campaign",
-                ));
-            }
+    let coin = match info.funds.first() {
+        Some(coin) => coin,
+        None => return Err(StdError::generic_err("No funds were sent")),
+    };
 
-            if campaign.amount < Uint128::one() {
-                CAMPAIGN_POOL.remove(deps.storage, campaign_id);
-                return Ok(Response::new().add_attribute("method", "cancel"));
-            }
+    let denom = Denom::Native(coin.denom.clone());
+    fund_campaign(
+        deps.storage,
+        &env,
+        campaign_id,
+        info.sender.clone(),
+        denom.clone(),
+        coin.amount,
+        start_time,
+        end_time,
+        signer,
+    )?;
+
+    let res = Response::new().add_attribute("method", "deposit");
+    assert_solvent(deps.as_ref(), &env, &res, vec![denom])?;
+    Ok(res)
+}
 
-            let amount = campaign.amount;
+pub fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, StdError> {
+    if wrapper.amount.is_zero() {
+        return Err(StdError::generic_err("No funds were sent"));
+    }
 
-            let native_denom = deps.querier.query_bonded_denom()?;
-            let res = Response::new()
-                .add_attribute("method", "cancel")
-                .add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: campaign.owner.clone().to_string(),
-                    amount: vec![Coin {
-                        denom: native_denom.clone(),
-                        amount,
-                    }],
-                }));
+    // info.sender is the token contract, wrapper.sender is the user who sent the tokens
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
 
-            CAMPAIGN_POOL.remove(deps.storage, campaign_id);
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Deposit {
+            campaign_id,
+            start_time,
+            end_time,
+            signer,
+            signer_type,
+        } => {
+            fund_campaign(
+                deps.storage,
+                &env,
+                campaign_id,
+                sender,
+                Denom::Cw20(info.sender.clone()),
+                wrapper.amount,
+                start_time,
+                end_time,
+                signer.map(|key| SignerKey {
+                    key_type: signer_type.unwrap_or_default(),
+                    key,
+                }),
+            )?;
 
+            let res = Response::new().add_attribute("method", "deposit");
+            assert_solvent(deps.as_ref(), &env, &res, vec![Denom::Cw20(info.sender)])?;
             Ok(res)
         }
-        None => Err(StdError::generic_err("Campaign does not exist")),
     }
 }
 
-pub fn set_cpool(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
+/// Credits a deposit to a campaign, creating it if it does not exist yet.
+/// The schedule and signer can only be given when the campaign is created.
+#[allow(clippy::too_many_arguments)]
+fn fund_campaign(
+    storage: &mut dyn Storage,
+    env: &Env,
     campaign_id: String,
+    owner: Addr,
+    denom: Denom,
     amount: Uint128,
-) -> Result<Response, StdError> {
-    let admin = ADMIN.load(deps.storage)?;
-
-    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
-        return Err(StdError::generic_err(
-            "Only contract owner can set the campaign pool",
-        ));
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+    signer: Option<SignerKey>,
+) -> Result<(), StdError> {
+    let denom_id = denom_id(&denom);
+    match ALLOWED_DENOMS.may_load(storage, &denom_id)? {
+        Some(allowed) => {
+            if let Some(min_deposit) = allowed.min_deposit {
+                if amount < min_deposit {
+                    return Err(StdError::generic_err(format!(
+                        "Deposit is below the minimum of {} {}",
+                        min_deposit, denom_id
+                    )));
+                }
+            }
+        }
+        None => {
+            return Err(StdError::generic_err(format!(
+                "Denom {} is not allowed",
+                denom_id
+            )))
+        }
     }
 
-    match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+    match CAMPAIGN_POOL.may_load(storage, campaign_id.clone())? {
         Some(mut campaign) => {
-            campaign.amount = amount;
-            CAMPAIGN_POOL.save(deps.storage, campaign_id, &campaign)
+            if campaign.denom != denom {
+                return Err(StdError::generic_err(
+                    "Campaign is funded with a different denom",
+                ));
+            }
+            if start_time.is_some() || end_time.is_some() {
+                return Err(StdError::generic_err(
+                    "Campaign schedule can only be set when the campaign is created",
+                ));
+            }
+            if signer.is_some() {
+                return Err(StdError::generic_err(
+                    "Campaign signer can only be set when the campaign is created",
+                ));
+            }
+            campaign.amount += amount;
+            CAMPAIGN_POOL.save(storage, campaign_id, &campaign)?;
         }
-        None => CAMPAIGN_POOL.save(
-            deps.storage,
-            campaign_id,
-            &Campaign {
-                owner: info.sender,
-                amount,
-            },
-        ),
-    }?;
+        None => {
+            if CLOSED_CAMPAIGNS.has(storage, &campaign_id) {
+                return Err(StdError::generic_err("Campaign id has been closed"));
+            }
+            if let Some(end_time) = end_time {
+                if end_time <= env.block.time {
+                    return Err(StdError::generic_err("End time must be in the future"));
+                }
+                if start_time.is_some_and(|start_time| start_time >= end_time) {
+                    return Err(StdError::generic_err("Start time must be before end time"));
+                }
+            }
+            if let Some(signer) = &signer {
+                validate_signer_key(signer)?;
+            }
+
+            CAMPAIGN_POOL.save(
+                storage,
+                campaign_id,
+                &Campaign {
+                    owner,
+                    amount,
+                    denom,
+                    start_time,
+                    end_time,
+                    merkle_root: None,
+                    signer,
+                },
+            )?;
+        }
+    }
 
-    Ok(Response::new().add_attribute("method", "set_cpool"))
+    allocate(storage, &denom_id, amount)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetCpool { campaign_id } => query_campaign_pool(deps, env, campaign_id),
-    }
+/// Adds funds credited to a campaign to the allocated total of their denom
+fn allocate(storage: &mut dyn Storage, denom_id: &str, amount: Uint128) -> StdResult<()> {
+    let allocated = ALLOCATED.may_load(storage, denom_id)?.unwrap_or_default();
+    ALLOCATED.save(storage, denom_id, &(allocated + amount))
 }
 
-pub fn query_campaign_pool(deps: Deps, _env: Env, campaign_id: String) -> StdResult<Binary> {
-    let campaign_pool = CAMPAIGN_POOL.may_load(deps.storage, campaign_id)?;
+/// Removes funds paid out of or refunded from a campaign from the allocated total
+fn release(storage: &mut dyn Storage, denom_id: &str, amount: Uint128) -> StdResult<()> {
+    let allocated = ALLOCATED.may_load(storage, denom_id)?.unwrap_or_default();
+    ALLOCATED.save(storage, denom_id, &allocated.checked_sub(amount)?)
+}
 
-    match campaign_pool {
-        Some(pool) => to_json_binary(&pool),
-        None => Err(StdError::generic_err("Campaign does not exist")),
+/// Builds the message paying `amount` of a campaign denom to `recipient`
+fn payout(denom: &Denom, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    match denom {
+        Denom::Cw20(token) => token_transfer(token, recipient, amount),
+        Denom::Native(denom) => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        })),
+    }
+}
+
+/// Identifies a denom in signed claims and the allow-list,
+/// CW20 tokens are identified by their contract address
+fn denom_id(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => denom.clone(),
+        Denom::Cw20(token) => token.to_string(),
     }
 }
+
+fn token_transfer(token: &Addr, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn claim(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    denom: String,
+    amount: Uint128,
+    nonce: u64,
+    recipient: Option<String>,
+    signature: Binary,
+    cosignatures: Option<Vec<Binary>>,
+    expires_at: Option<Expiration>,
+) -> Result<Response, StdError> {
+    let recipient = recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
+
+    // vouchers without a recipient keep their original format and pay the sender
+    let campaign = process_claim(
+        deps.branch(),
+        &env,
+        SignedData {
+            campaign_id,
+            nonce,
+            denom,
+            amount,
+            sender: info.sender.clone(),
+            recipient: recipient.clone(),
+            relayer_fee: None,
+            expires_at,
+        },
+        &signature,
+        cosignatures,
+        false,
+    )?;
+
+    // Send funds
+    let recipient = recipient.unwrap_or(info.sender);
+    let res = Response::new()
+        .add_attribute("method", "claim")
+        .add_attribute("recipient", recipient.to_string())
+        .add_message(payout(&campaign.denom, recipient.to_string(), amount)?);
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+/// Submits a voucher on behalf of its recipient, e.g. for users without gas tokens.
+/// The relayer is paid the signed `relayer_fee` out of the claimed amount.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_for(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    denom: String,
+    amount: Uint128,
+    nonce: u64,
+    recipient: String,
+    relayer_fee: Option<Uint128>,
+    signature: Binary,
+    cosignatures: Option<Vec<Binary>>,
+    expires_at: Option<Expiration>,
+) -> Result<Response, StdError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let fee = relayer_fee.unwrap_or_default();
+    // the recipient has to receive something, a fee taking it all would leave an empty payout
+    if fee >= amount {
+        return Err(StdError::generic_err(
+            "Relayer fee must be less than the claimed amount",
+        ));
+    }
+
+    // relayed vouchers commit to the recipient explicitly,
+    // so ordinary vouchers cannot be submitted by third parties
+    let campaign = process_claim(
+        deps.branch(),
+        &env,
+        SignedData {
+            campaign_id,
+            nonce,
+            denom,
+            amount,
+            sender: recipient.clone(),
+            recipient: Some(recipient.clone()),
+            relayer_fee,
+            expires_at,
+        },
+        &signature,
+        cosignatures,
+        false,
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "claim_for")
+        .add_attribute("relayer", info.sender.to_string())
+        .add_message(payout(
+            &campaign.denom,
+            recipient.to_string(),
+            amount - fee,
+        )?);
+    if !fee.is_zero() {
+        res = res.add_message(payout(&campaign.denom, info.sender.to_string(), fee)?);
+    }
+
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+/// Processes several signed claims at once, paying out a single message per denom.
+/// Fails as a whole if any of the claims is invalid.
+pub fn claim_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claims: Vec<ClaimItem>,
+) -> Result<Response, StdError> {
+    if claims.is_empty() {
+        return Err(StdError::generic_err("No claims were sent"));
+    }
+    if claims.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "Cannot claim more than {} campaigns at once",
+            MAX_LIMIT
+        )));
+    }
+
+    let claims: Vec<(SignedData, Binary, Option<Vec<Binary>>)> = claims
+        .into_iter()
+        .map(|item| {
+            (
+                SignedData {
+                    campaign_id: item.campaign_id,
+                    nonce: item.nonce,
+                    denom: item.denom,
+                    amount: item.amount,
+                    sender: info.sender.clone(),
+                    recipient: None,
+                    relayer_fee: None,
+                    expires_at: item.expires_at,
+                },
+                item.signature,
+                item.cosignatures,
+            )
+        })
+        .collect();
+    let verified = batch_verify_ed25519(deps.as_ref(), &env, &claims)?;
+
+    let mut totals: Vec<(Denom, Uint128)> = vec![];
+    for ((data, signature, cosignatures), verified) in claims.into_iter().zip(verified) {
+        let amount = data.amount;
+        let campaign = process_claim(
+            deps.branch(),
+            &env,
+            data,
+            &signature,
+            cosignatures,
+            verified,
+        )?;
+
+        match totals
+            .iter_mut()
+            .find(|(denom, _)| *denom == campaign.denom)
+        {
+            Some((_, total)) => *total += amount,
+            None => totals.push((campaign.denom, amount)),
+        }
+    }
+
+    let mut res = Response::new().add_attribute("method", "claim_batch");
+    for (denom, amount) in totals {
+        res = res.add_message(payout(&denom, info.sender.to_string(), amount)?);
+    }
+
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+/// Checks the signatures of claims approved by a single ed25519 key in one call.
+/// Returns which claims were verified, none of them if any signature in the batch is invalid
+/// so they are checked one by one instead.
+fn batch_verify_ed25519(
+    deps: Deps,
+    env: &Env,
+    claims: &[(SignedData, Binary, Option<Vec<Binary>>)],
+) -> StdResult<Vec<bool>> {
+    let mut verified = vec![false; claims.len()];
+    let mut messages = vec![];
+    let mut signatures = vec![];
+    let mut keys = vec![];
+
+    for (i, (data, signature, cosignatures)) in claims.iter().enumerate() {
+        if cosignatures.is_some() || signature.len() != 64 {
+            continue;
+        }
+
+        let (signers, required) =
+            claim_signers(deps, env, &data.campaign_id, &data.denom, data.amount)?;
+        if required != 1 {
+            continue;
+        }
+
+        // with several ed25519 signers it is not known which one signed
+        let mut ed25519 = signers
+            .into_iter()
+            .flatten()
+            .filter(|signer| signer.key_type == KeyType::Ed25519);
+        if let (Some(signer), None) = (ed25519.next(), ed25519.next()) {
+            messages.push(signed_document(env, data)?.into_bytes());
+            signatures.push(signature.as_slice());
+            keys.push(signer.key);
+            verified[i] = true;
+        }
+    }
+
+    if messages.len() < 2 {
+        return Ok(vec![false; claims.len()]);
+    }
+
+    let messages: Vec<&[u8]> = messages.iter().map(|message| message.as_slice()).collect();
+    let keys: Vec<&[u8]> = keys.iter().map(|key| key.as_slice()).collect();
+    if !deps
+        .api
+        .ed25519_batch_verify(&messages, &signatures, &keys)?
+    {
+        return Ok(vec![false; claims.len()]);
+    }
+
+    Ok(verified)
+}
+
+/// Returns the signers that can approve a claim of `amount` in `denom` on the campaign,
+/// and how many of them are needed
+fn claim_signers(
+    deps: Deps,
+    env: &Env,
+    campaign_id: &str,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<(Vec<Vec<SignerKey>>, u32)> {
+    // campaigns with their own signer are authorized by their owner alone
+    let campaign = CAMPAIGN_POOL.may_load(deps.storage, campaign_id.to_string())?;
+    if let Some(signer) = campaign.and_then(|campaign| campaign.signer) {
+        return Ok((vec![vec![signer]], 1));
+    }
+
+    let required = match APPROVAL_THRESHOLDS.may_load(deps.storage, denom)? {
+        Some(threshold) if amount > threshold => {
+            REQUIRED_SIGNATURES.may_load(deps.storage)?.unwrap_or(1)
+        }
+        _ => 1,
+    };
+
+    Ok((active_signers(deps, env)?, required))
+}
+
+/// Checks a signed claim, marks its nonce as used and debits the campaign.
+/// `verified` skips the signature check for claims that were batch verified already.
+fn process_claim(
+    deps: DepsMut,
+    env: &Env,
+    data: SignedData,
+    signature: &Binary,
+    cosignatures: Option<Vec<Binary>>,
+    verified: bool,
+) -> Result<Campaign, StdError> {
+    if data
+        .expires_at
+        .is_some_and(|expires_at| expires_at.is_expired(&env.block))
+    {
+        return Err(StdError::generic_err("Voucher has expired"));
+    }
+
+    // Check if nonce has been used or revoked
+    match nonce_status(deps.storage, &data.campaign_id, &data.sender, data.nonce)? {
+        NonceStatus::Unused => {
+            let key = (data.campaign_id.as_str(), &data.sender, data.nonce / 64);
+            let claimed = NONCES.may_load(deps.storage, key)?.unwrap_or_default();
+            NONCES.save(deps.storage, key, &(claimed | 1u64 << (data.nonce % 64)))?;
+        }
+        NonceStatus::Claimed => return Err(StdError::generic_err("Nonce has been used")),
+        NonceStatus::Revoked => return Err(StdError::generic_err("Nonce has been revoked")),
+    }
+
+    // Check if data is correctly signed, by several signers for large claims
+    if !verified {
+        let (signers, required) = claim_signers(
+            deps.as_ref(),
+            env,
+            &data.campaign_id,
+            &data.denom,
+            data.amount,
+        )?;
+        let mut signatures = vec![signature.clone()];
+        signatures.extend(cosignatures.unwrap_or_default());
+        verify_arbitrary(deps.as_ref(), env, &data, &signatures, signers, required)?;
+    }
+
+    let campaign = debit_campaign(
+        deps.storage,
+        env,
+        data.campaign_id,
+        &data.denom,
+        data.amount,
+    )?;
+    ensure_signed_claims(&campaign)?;
+
+    Ok(campaign)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn claim_cumulative(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    denom: String,
+    total: Uint128,
+    signature: Binary,
+    cosignatures: Option<Vec<Binary>>,
+    expires_at: Option<Expiration>,
+) -> Result<Response, StdError> {
+    if expires_at.is_some_and(|expires_at| expires_at.is_expired(&env.block)) {
+        return Err(StdError::generic_err("Voucher has expired"));
+    }
+
+    // Only the part of the total that has not been paid out yet is claimable
+    let claimed = CLAIMED
+        .may_load(deps.storage, (&campaign_id, &info.sender))?
+        .unwrap_or_default();
+
+    // Check if data is correctly signed, by several signers for large claims
+    let (signers, required) = claim_signers(
+        deps.as_ref(),
+        &env,
+        &campaign_id,
+        &denom,
+        total.saturating_sub(claimed),
+    )?;
+    let mut signatures = vec![signature];
+    signatures.extend(cosignatures.unwrap_or_default());
+    verify_arbitrary(
+        deps.as_ref(),
+        &env,
+        &SignedCumulativeData {
+            campaign_id: campaign_id.clone(),
+            denom: denom.clone(),
+            total,
+            sender: info.sender.clone(),
+            expires_at,
+        },
+        &signatures,
+        signers,
+        required,
+    )?;
+
+    if total <= claimed {
+        return Err(StdError::generic_err("Nothing to claim"));
+    }
+    let amount = total - claimed;
+    CLAIMED.save(deps.storage, (&campaign_id, &info.sender), &total)?;
+
+    let campaign = debit_campaign(deps.storage, &env, campaign_id, &denom, amount)?;
+    ensure_signed_claims(&campaign)?;
+
+    // Send funds
+    let res = Response::new()
+        .add_attribute("method", "claim_cumulative")
+        .add_attribute("amount", amount)
+        .add_message(payout(&campaign.denom, info.sender.to_string(), amount)?);
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+pub fn register_merkle_root(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    merkle_root: HexBinary,
+) -> Result<Response, StdError> {
+    let mut campaign = match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+        Some(campaign) => campaign,
+        None => return Err(StdError::generic_err("Campaign does not exist")),
+    };
+
+    if info.sender != campaign.owner {
+        return Err(StdError::generic_err(
+            "Only campaign owner can register a Merkle root",
+        ));
+    }
+
+    if campaign.merkle_root.is_some() {
+        return Err(StdError::generic_err("Merkle root is already registered"));
+    }
+
+    if merkle_root.len() != 32 {
+        return Err(StdError::generic_err("Merkle root must be a sha256 hash"));
+    }
+
+    campaign.merkle_root = Some(merkle_root.clone());
+    CAMPAIGN_POOL.save(deps.storage, campaign_id.clone(), &campaign)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "register_merkle_root")
+        .add_attribute("campaign_id", campaign_id)
+        .add_attribute("merkle_root", merkle_root.to_hex()))
+}
+
+pub fn claim_merkle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    amount: Uint128,
+    index: u32,
+    proof: Vec<HexBinary>,
+) -> Result<Response, StdError> {
+    let campaign = match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+        Some(campaign) => campaign,
+        None => return Err(StdError::generic_err("Campaign ID not valid")),
+    };
+
+    match &campaign.merkle_root {
+        Some(merkle_root) => verify_merkle_proof(merkle_root, &info.sender, amount, index, &proof)?,
+        None => {
+            return Err(StdError::generic_err(
+                "Campaign has no Merkle root registered",
+            ))
+        }
+    }
+
+    // Check if the leaf has been claimed
+    let word = index / 64;
+    let bit = 1u64 << (index % 64);
+    let claimed = MERKLE_CLAIMED
+        .may_load(deps.storage, (&campaign_id, word))?
+        .unwrap_or_default();
+    if claimed & bit != 0 {
+        return Err(StdError::generic_err("Already claimed"));
+    }
+    MERKLE_CLAIMED.save(deps.storage, (&campaign_id, word), &(claimed | bit))?;
+
+    let denom = denom_id(&campaign.denom);
+    let campaign = debit_campaign(deps.storage, &env, campaign_id, &denom, amount)?;
+
+    // Send funds
+    let res = Response::new()
+        .add_attribute("method", "claim_merkle")
+        .add_message(payout(&campaign.denom, info.sender.to_string(), amount)?);
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+fn ensure_signed_claims(campaign: &Campaign) -> Result<(), StdError> {
+    if campaign.merkle_root.is_some() {
+        return Err(StdError::generic_err(
+            "Campaign can only be claimed with a Merkle proof",
+        ));
+    }
+    Ok(())
+}
+
+/// Takes a claimed amount out of an active campaign funded in `denom`
+fn debit_campaign(
+    storage: &mut dyn Storage,
+    env: &Env,
+    campaign_id: String,
+    denom: &str,
+    amount: Uint128,
+) -> Result<Campaign, StdError> {
+    // Check if campaign exists and has funds
+    let mut campaign = match CAMPAIGN_POOL.may_load(storage, campaign_id.clone())? {
+        Some(campaign) => campaign,
+        None => return Err(StdError::generic_err("Campaign ID not valid")),
+    };
+
+    match campaign.status(env.block.time) {
+        CampaignStatus::Pending => {
+            return Err(StdError::generic_err("Campaign has not started yet"))
+        }
+        CampaignStatus::Ended => return Err(StdError::generic_err("Campaign has ended")),
+        CampaignStatus::Active => {}
+    }
+
+    // Check if denom requested is the one the campaign was funded with
+    if denom_id(&campaign.denom) != denom {
+        return Err(StdError::generic_err("Invalid denom"));
+    }
+
+    if campaign.amount < amount {
+        return Err(StdError::generic_err("Campaign does not have enough funds"));
+    }
+
+    // a drained campaign is kept at zero so its id, signer and claims stay with its owner
+    campaign.amount -= amount;
+    CAMPAIGN_POOL.save(storage, campaign_id, &campaign)?;
+    release(storage, denom, amount)?;
+
+    Ok(campaign)
+}
+
+/// Takes a campaign out of the pool, keeping its id so its nonces can be pruned later
+fn remove_campaign(storage: &mut dyn Storage, campaign_id: String) -> StdResult<()> {
+    CAMPAIGN_POOL.remove(storage, campaign_id.clone());
+    REMOVED_CAMPAIGNS.save(storage, &campaign_id, &Empty {})
+}
+
+/// Campaign amounts summed per denom id
+type DenomTotals = BTreeMap<String, (Denom, Uint128)>;
+
+/// Sums the amounts of up to `limit` campaigns after `start_after` per denom id,
+/// along with the id of the last campaign read if the page is full
+fn campaign_totals(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<usize>,
+) -> StdResult<(DenomTotals, Option<String>)> {
+    let campaigns = CAMPAIGN_POOL.range(
+        storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    );
+
+    let mut totals = DenomTotals::new();
+    let mut last = None;
+    let mut read = 0;
+    for item in campaigns.take(limit.unwrap_or(usize::MAX)) {
+        let (campaign_id, campaign) = item?;
+        totals
+            .entry(denom_id(&campaign.denom))
+            .or_insert((campaign.denom, Uint128::zero()))
+            .1 += campaign.amount;
+        last = Some(campaign_id);
+        read += 1;
+    }
+
+    Ok((totals, last.filter(|_| Some(read) == limit)))
+}
+
+/// Checks that the campaign pools of `denoms` and of the funds `res` pays out add up to their
+/// allocated totals and are covered by the balance left once `res` has been paid out.
+/// Only runs in unit tests and builds with the `solvency-check` feature.
+fn assert_solvent(deps: Deps, env: &Env, res: &Response, denoms: Vec<Denom>) -> StdResult<()> {
+    if !cfg!(any(test, feature = "solvency-check")) {
+        return Ok(());
+    }
+
+    let mut touched: BTreeMap<String, Denom> = denoms
+        .into_iter()
+        .map(|denom| (denom_id(&denom), denom))
+        .collect();
+    let mut outgoing: BTreeMap<String, Uint128> = BTreeMap::new();
+    for sub_msg in &res.messages {
+        match &sub_msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+                for coin in amount {
+                    *outgoing.entry(coin.denom.clone()).or_default() += coin.amount;
+                    touched.insert(coin.denom.clone(), Denom::Native(coin.denom.clone()));
+                }
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                if let Ok(Cw20ExecuteMsg::Transfer { amount, .. }) = from_json(msg) {
+                    *outgoing.entry(contract_addr.clone()).or_default() += amount;
+                    touched.insert(
+                        contract_addr.clone(),
+                        Denom::Cw20(Addr::unchecked(contract_addr)),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // every campaign is read, which is why the check is left out of default builds
+    let (totals, _) = campaign_totals(deps.storage, None, None)?;
+    for (denom_id, denom) in touched {
+        let campaigns = totals
+            .get(&denom_id)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default();
+        let allocated = ALLOCATED
+            .may_load(deps.storage, &denom_id)?
+            .unwrap_or_default();
+        if campaigns != allocated {
+            return Err(StdError::generic_err(format!(
+                "Campaign pools in {} add up to {} but {} is allocated",
+                denom_id, campaigns, allocated
+            )));
+        }
+        if allocated.is_zero() {
+            continue;
+        }
+
+        let available = denom_balance(deps, env, &denom)?
+            .saturating_sub(outgoing.get(&denom_id).copied().unwrap_or_default());
+        if allocated > available {
+            return Err(StdError::generic_err(format!(
+                "Contract is insolvent in {}: {} allocated, {} available",
+                denom_id, allocated, available
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Withdraws funds that are not allocated to any campaign
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    denom: Option<String>,
+    token: Option<String>,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::Treasurer)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a treasurer can withdraw",
+        ));
+    }
+    // withdrawals always go to the admin, not to the treasurer
+    let admin = ADMIN.load(deps.storage)?;
+
+    let (denom, own_balance) = contract_balance(deps.as_ref(), &env, denom, token)?;
+    let allocated = ALLOCATED
+        .may_load(deps.storage, &denom_id(&denom))?
+        .unwrap_or_default();
+
+    if amount > own_balance.saturating_sub(allocated) {
+        return Err(StdError::generic_err(
+            "Not enough unallocated funds in the contract",
+        ));
+    }
+
+    let res = Response::new()
+        .add_attribute("method", "withdraw")
+        .add_message(payout(
+            &denom,
+            deps.api.addr_humanize(&admin)?.to_string(),
+            amount,
+        )?);
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+/// Withdraws funds regardless of what is allocated to campaigns, the claims and refunds
+/// of the affected campaigns fail until the contract is topped up again
+pub fn emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    denom: Option<String>,
+    token: Option<String>,
+    reason: String,
+) -> Result<Response, StdError> {
+    // unbacking campaigns is not delegated, unlike ordinary withdrawals
+    let admin = ADMIN.load(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can make emergency withdrawals",
+        ));
+    }
+
+    if reason.trim().is_empty() {
+        return Err(StdError::generic_err("Emergency withdrawals need a reason"));
+    }
+
+    let (denom, own_balance) = contract_balance(deps.as_ref(), &env, denom, token)?;
+    if amount > own_balance {
+        return Err(StdError::generic_err("Not enough funds in the contract"));
+    }
+
+    let denom_id = denom_id(&denom);
+    let allocated = ALLOCATED
+        .may_load(deps.storage, &denom_id)?
+        .unwrap_or_default();
+
+    Ok(Response::new()
+        .add_attribute("method", "emergency_withdraw")
+        .add_event(
+            Event::new("emergency_withdraw")
+                .add_attribute("denom", denom_id)
+                .add_attribute("amount", amount)
+                .add_attribute("allocated", allocated)
+                .add_attribute("reason", reason),
+        )
+        .add_message(payout(
+            &denom,
+            deps.api.addr_humanize(&admin)?.to_string(),
+            amount,
+        )?))
+}
+
+/// Balance of the contract in a native denom, the staking denom if not set, or a CW20 token
+fn contract_balance(
+    deps: Deps,
+    env: &Env,
+    denom: Option<String>,
+    token: Option<String>,
+) -> StdResult<(Denom, Uint128)> {
+    if let Some(token) = token {
+        if denom.is_some() {
+            return Err(StdError::generic_err(
+                "Either denom or token can be withdrawn, not both",
+            ));
+        }
+
+        let denom = Denom::Cw20(deps.api.addr_validate(&token)?);
+        let own_balance = denom_balance(deps, env, &denom)?;
+        return Ok((denom, own_balance));
+    }
+
+    let denom = Denom::Native(match denom {
+        Some(denom) => denom,
+        None => deps.querier.query_bonded_denom()?,
+    });
+    let own_balance = denom_balance(deps, env, &denom)?;
+
+    Ok((denom, own_balance))
+}
+
+fn denom_balance(deps: Deps, env: &Env, denom: &Denom) -> StdResult<Uint128> {
+    match denom {
+        Denom::Native(denom) => Ok(deps
+            .querier
+            .query_balance(&env.contract.address, denom)
+            .unwrap_or_default()
+            .amount),
+        Denom::Cw20(token) => {
+            let own_balance: BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(own_balance.balance)
+        }
+    }
+}
+
+pub fn cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+) -> Result<Response, StdError> {
+    let admin = ADMIN.load(deps.storage)?;
+    let is_admin = deps.api.addr_canonicalize(info.sender.as_str())? == admin;
+
+    match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+        Some(campaign) => {
+            if !is_admin && info.sender != campaign.owner {
+                return Err(StdError::generic_err(
+                    "Only campaign owner can cancel the campaign",
+                ));
+            }
+
+            if campaign.amount < Uint128::one() {
+                remove_campaign(deps.storage, campaign_id)?;
+                return Ok(Response::new().add_attribute("method", "cancel"));
+            }
+            release(deps.storage, &denom_id(&campaign.denom), campaign.amount)?;
+
+            let res = Response::new()
+                .add_attribute("method", "cancel")
+                .add_message(payout(
+                    &campaign.denom,
+                    campaign.owner.to_string(),
+                    campaign.amount,
+                )?);
+
+            remove_campaign(deps.storage, campaign_id)?;
+
+            // the admin cancels campaigns left unbacked by an emergency withdrawal
+            if !is_admin {
+                assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+            }
+            Ok(res)
+        }
+        None => Err(StdError::generic_err("Campaign does not exist")),
+    }
+}
+
+/// Enables or disables signatures that are not bound to a signing domain
+pub fn set_legacy_signatures(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    LEGACY_SIGNATURES.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_legacy_signatures")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Changes the bech32 prefix used for the signer address of claim signatures
+pub fn set_hrp(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hrp: String,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    validate_hrp(&hrp)?;
+    HRP.save(deps.storage, &hrp)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_hrp")
+        .add_attribute("hrp", hrp))
+}
+
+/// Replaces the claim signing key, optionally keeping the old one valid for `grace_period` seconds
+pub fn rotate_signer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+    grace_period: Option<u64>,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    validate_pubkey(&pubkey)?;
+
+    let previous = PUBKEY.load(deps.storage)?;
+    if previous == pubkey {
+        return Err(StdError::generic_err("Key is already the active signer"));
+    }
+    if SIGNERS.has(deps.storage, &pubkey) {
+        return Err(StdError::generic_err(
+            "Key is already registered as a co-signer",
+        ));
+    }
+
+    PUBKEY.save(deps.storage, &pubkey)?;
+    match grace_period {
+        Some(seconds) if seconds > 0 => PREVIOUS_PUBKEY.save(
+            deps.storage,
+            &GraceKey {
+                pubkey: previous,
+                expires_at: env.block.time.plus_seconds(seconds),
+            },
+        )?,
+        // a compromised key is revoked right away
+        _ => PREVIOUS_PUBKEY.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "rotate_signer")
+        .add_attribute("pubkey", pubkey.to_base64())
+        .add_attribute("grace_period", grace_period.unwrap_or_default().to_string()))
+}
+
+/// Registers an additional signer, counted as a distinct approval for large claims
+pub fn add_signer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+    key_type: KeyType,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    let signer = SignerKey {
+        key_type,
+        key: pubkey,
+    };
+    validate_signer_key(&signer)?;
+
+    if PUBKEY.load(deps.storage)? == signer.key || SIGNERS.has(deps.storage, &signer.key) {
+        return Err(StdError::generic_err("Signer is already registered"));
+    }
+    // the rotated out key still signs for the backend until its grace period ends
+    if PREVIOUS_PUBKEY
+        .may_load(deps.storage)?
+        .is_some_and(|previous| {
+            previous.pubkey == signer.key && env.block.time < previous.expires_at
+        })
+    {
+        return Err(StdError::generic_err(
+            "Signer is still accepted as the previous backend key",
+        ));
+    }
+
+    SIGNERS.save(deps.storage, &signer.key, &signer.key_type)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_signer")
+        .add_attribute("pubkey", signer.key.to_base64()))
+}
+
+pub fn remove_signer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    if !SIGNERS.has(deps.storage, &pubkey) {
+        return Err(StdError::generic_err("Signer is not registered"));
+    }
+
+    // large claims must stay claimable with the remaining signers
+    let required = REQUIRED_SIGNATURES.may_load(deps.storage)?.unwrap_or(1);
+    if required >= signer_count(deps.storage) {
+        return Err(StdError::generic_err(
+            "Cannot remove a signer needed for the required signatures",
+        ));
+    }
+
+    SIGNERS.remove(deps.storage, &pubkey);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_signer")
+        .add_attribute("pubkey", pubkey.to_base64()))
+}
+
+pub fn set_required_signatures(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    required: u32,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    if required == 0 || required > signer_count(deps.storage) {
+        return Err(StdError::generic_err(
+            "Required signatures must be between 1 and the number of signers",
+        ));
+    }
+
+    REQUIRED_SIGNATURES.save(deps.storage, &required)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_required_signatures")
+        .add_attribute("required", required.to_string()))
+}
+
+pub fn set_approval_threshold(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+    threshold: Option<Uint128>,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::SignerManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings",
+        ));
+    }
+
+    match threshold {
+        Some(threshold) => APPROVAL_THRESHOLDS.save(deps.storage, &denom, &threshold)?,
+        None => APPROVAL_THRESHOLDS.remove(deps.storage, &denom),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_approval_threshold")
+        .add_attribute("denom", denom)
+        .add_attribute(
+            "threshold",
+            threshold.map_or("none".to_string(), |t| t.to_string()),
+        ))
+}
+
+/// The backend key plus the registered co-signers
+fn signer_count(storage: &dyn Storage) -> u32 {
+    1 + SIGNERS.keys(storage, None, None, Order::Ascending).count() as u32
+}
+
+/// Whether `sender` is the admin, a signer manager or the account of an active secp256k1 signer
+fn can_revoke_nonces(deps: Deps, env: &Env, sender: &Addr) -> StdResult<bool> {
+    if has_role(deps, sender, Role::SignerManager)? {
+        return Ok(true);
+    }
+
+    let hrp = HRP.load(deps.storage)?;
+    Ok(active_signers(deps, env)?
+        .into_iter()
+        .flatten()
+        .filter(|signer| signer.key_type == KeyType::Secp256k1)
+        .any(|signer| pubkey_to_account(&signer.key, &hrp) == sender.as_str()))
+}
+
+pub fn revoke_nonces(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    sender: String,
+    nonces: Vec<u64>,
+) -> Result<Response, StdError> {
+    if !can_revoke_nonces(deps.as_ref(), &env, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the admin, a signer manager or a signer can revoke nonces",
+        ));
+    }
+
+    if nonces.is_empty() {
+        return Err(StdError::generic_err("No nonces to revoke"));
+    }
+
+    // Claimed nonces keep their status
+    let sender = deps.api.addr_validate(&sender)?;
+    let mut revoked = 0u32;
+    for nonce in nonces {
+        if nonce_status(deps.storage, &campaign_id, &sender, nonce)? == NonceStatus::Unused {
+            let key = (campaign_id.as_str(), &sender, nonce / 64);
+            let word = REVOKED_NONCES
+                .may_load(deps.storage, key)?
+                .unwrap_or_default();
+            REVOKED_NONCES.save(deps.storage, key, &(word | 1u64 << (nonce % 64)))?;
+            revoked += 1;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "revoke_nonces")
+        .add_attribute("revoked", revoked.to_string()))
+}
+
+fn nonce_status(
+    storage: &dyn Storage,
+    campaign_id: &str,
+    sender: &Addr,
+    nonce: u64,
+) -> StdResult<NonceStatus> {
+    let key = (campaign_id, sender, nonce / 64);
+    let bit = 1u64 << (nonce % 64);
+    if NONCES.may_load(storage, key)?.unwrap_or_default() & bit != 0 {
+        Ok(NonceStatus::Claimed)
+    } else if REVOKED_NONCES.may_load(storage, key)?.unwrap_or_default() & bit != 0 {
+        Ok(NonceStatus::Revoked)
+    } else {
+        Ok(NonceStatus::Unused)
+    }
+}
+
+/// Deletes the nonces of a campaign that was cancelled or swept, its id cannot be funded
+/// again afterwards so pruned vouchers cannot be replayed. Without a campaign id the global
+/// nonces left by the 0.1.2 release are deleted.
+pub fn prune_nonces(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, StdError> {
+    let admin = ADMIN.load(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can prune nonces",
+        ));
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT).min(MAX_PRUNE_LIMIT) as usize;
+
+    let campaign_id = match campaign_id {
+        Some(campaign_id) => campaign_id,
+        None => {
+            let keys = LEGACY_NONCES
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for nonce in &keys {
+                LEGACY_NONCES.remove(deps.storage, nonce);
+            }
+
+            return Ok(Response::new()
+                .add_attribute("method", "prune_nonces")
+                .add_attribute("pruned", keys.len().to_string()));
+        }
+    };
+
+    if CAMPAIGN_POOL.has(deps.storage, campaign_id.clone()) {
+        return Err(StdError::generic_err("Campaign is still open"));
+    }
+    // ids that never held a campaign cannot be closed ahead of time
+    if REMOVED_CAMPAIGNS.has(deps.storage, &campaign_id) {
+        REMOVED_CAMPAIGNS.remove(deps.storage, &campaign_id);
+        CLOSED_CAMPAIGNS.save(deps.storage, &campaign_id, &Empty {})?;
+    } else if !CLOSED_CAMPAIGNS.has(deps.storage, &campaign_id) {
+        return Err(StdError::generic_err("Campaign does not exist"));
+    }
+
+    let mut pruned = 0;
+    for bitmap in [NONCES, REVOKED_NONCES] {
+        let keys = bitmap
+            .sub_prefix(&campaign_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - pruned)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (sender, word) in &keys {
+            bitmap.remove(deps.storage, (&campaign_id, sender, *word));
+        }
+        pruned += keys.len();
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "prune_nonces")
+        .add_attribute("campaign_id", campaign_id)
+        .add_attribute("pruned", pruned.to_string()))
+}
+
+/// Refunds the remaining funds of ended campaigns to their owners, callable by anyone.
+/// Campaigns that do not exist or have not ended are skipped.
+pub fn sweep_expired(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    campaign_ids: Vec<String>,
+) -> Result<Response, StdError> {
+    if campaign_ids.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "Cannot sweep more than {} campaigns at once",
+            MAX_LIMIT
+        )));
+    }
+
+    let mut res = Response::new().add_attribute("method", "sweep_expired");
+
+    for campaign_id in campaign_ids {
+        // a campaign swept by someone else first must not fail the whole batch
+        let campaign = match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+            Some(campaign) if campaign.status(env.block.time) == CampaignStatus::Ended => campaign,
+            Some(_) => {
+                res = res.add_event(sweep_skipped(campaign_id, "not_ended"));
+                continue;
+            }
+            None => {
+                res = res.add_event(sweep_skipped(campaign_id, "not_found"));
+                continue;
+            }
+        };
+
+        if !campaign.amount.is_zero() {
+            res = res.add_message(payout(
+                &campaign.denom,
+                campaign.owner.to_string(),
+                campaign.amount,
+            )?);
+        }
+
+        remove_campaign(deps.storage, campaign_id.clone())?;
+        release(deps.storage, &denom_id(&campaign.denom), campaign.amount)?;
+
+        res = res.add_event(
+            Event::new("sweep_expired")
+                .add_attribute("campaign_id", campaign_id)
+                .add_attribute("owner", campaign.owner)
+                .add_attribute("amount", campaign.amount),
+        );
+    }
+
+    assert_solvent(deps.as_ref(), &env, &res, vec![])?;
+    Ok(res)
+}
+
+fn sweep_skipped(campaign_id: String, reason: &str) -> Event {
+    Event::new("sweep_skipped")
+        .add_attribute("campaign_id", campaign_id)
+        .add_attribute("reason", reason)
+}
+
+/// Moves funds between the unallocated reserve and a campaign, creating it in the staking
+/// denom and owned by the admin if it does not exist yet.
+/// Increases have to be covered by the reserve.
+pub fn set_cpool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: String,
+    amount: Uint128,
+) -> Result<Response, StdError> {
+    if !has_role(deps.as_ref(), &info.sender, Role::PoolManager)? {
+        return Err(StdError::generic_err(
+            "Only the admin or a pool manager can set the campaign pool",
+        ));
+    }
+    let admin = ADMIN.load(deps.storage)?;
+    let is_admin = deps.api.addr_canonicalize(info.sender.as_str())? == admin;
+
+    let mut campaign = match CAMPAIGN_POOL.may_load(deps.storage, campaign_id.clone())? {
+        Some(campaign) => campaign,
+        None if CLOSED_CAMPAIGNS.has(deps.storage, &campaign_id) => {
+            return Err(StdError::generic_err("Campaign id has been closed"))
+        }
+        // pool managers cannot own campaigns, or they could cancel them to refund themselves
+        None => Campaign {
+            owner: deps.api.addr_humanize(&admin)?,
+            amount: Uint128::zero(),
+            denom: Denom::Native(deps.querier.query_bonded_denom()?),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
+        },
+    };
+
+    let denom_id = denom_id(&campaign.denom);
+    if amount > campaign.amount {
+        let allocated = ALLOCATED
+            .may_load(deps.storage, &denom_id)?
+            .unwrap_or_default();
+        let reserve =
+            denom_balance(deps.as_ref(), &env, &campaign.denom)?.saturating_sub(allocated);
+        if amount - campaign.amount > reserve {
+            return Err(StdError::generic_err(
+                "Not enough unallocated funds to back the campaign pool",
+            ));
+        }
+    }
+
+    let old_amount = campaign.amount;
+    release(deps.storage, &denom_id, old_amount)?;
+    allocate(deps.storage, &denom_id, amount)?;
+    campaign.amount = amount;
+    CAMPAIGN_POOL.save(deps.storage, campaign_id.clone(), &campaign)?;
+
+    let res = Response::new()
+        .add_attribute("method", "set_cpool")
+        .add_event(
+            Event::new("set_cpool")
+                .add_attribute("campaign_id", campaign_id)
+                .add_attribute("denom", denom_id)
+                .add_attribute("old_amount", old_amount)
+                .add_attribute("new_amount", amount),
+        );
+    // the admin shrinks campaigns left unbacked by an emergency withdrawal
+    if !is_admin {
+        assert_solvent(deps.as_ref(), &env, &res, vec![campaign.denom])?;
+    }
+    Ok(res)
+}
+
+pub fn add_denom(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+    min_deposit: Option<Uint128>,
+) -> Result<Response, StdError> {
+    let admin = ADMIN.load(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can manage denoms",
+        ));
+    }
+
+    ALLOWED_DENOMS.save(deps.storage, &denom, &AllowedDenom { min_deposit })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_denom")
+        .add_attribute("denom", denom))
+}
+
+pub fn remove_denom(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, StdError> {
+    let admin = ADMIN.load(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != admin {
+        return Err(StdError::generic_err(
+            "Only contract owner can manage denoms",
+        ));
+    }
+
+    if !ALLOWED_DENOMS.has(deps.storage, &denom) {
+        return Err(StdError::generic_err("Denom is not in the allow-list"));
+    }
+
+    // existing campaigns in this denom can still be claimed and cancelled
+    ALLOWED_DENOMS.remove(deps.storage, &denom);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_denom")
+        .add_attribute("denom", denom))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetCpool { campaign_id } => query_campaign_pool(deps, env, campaign_id),
+        QueryMsg::ListDenoms { start_after, limit } => query_denoms(deps, env, start_after, limit),
+        QueryMsg::Claimed {
+            campaign_id,
+            address,
+        } => query_claimed(deps, env, campaign_id, address),
+        QueryMsg::MerkleClaimed { campaign_id, index } => {
+            query_merkle_claimed(deps, env, campaign_id, index)
+        }
+        QueryMsg::SigningDomain {} => query_signing_domain(deps, env),
+        QueryMsg::SignerKeys {} => query_signer_keys(deps, env),
+        QueryMsg::ApprovalPolicy {} => query_approval_policy(deps, env),
+        QueryMsg::Admin {} => query_admin(deps, env),
+        QueryMsg::ListRoles { start_after, limit } => query_roles(deps, env, start_after, limit),
+        QueryMsg::Solvency { start_after, limit } => query_solvency(deps, env, start_after, limit),
+        QueryMsg::Nonces {
+            campaign_id,
+            sender,
+            nonces,
+        } => query_nonces(deps, env, campaign_id, sender, nonces),
+    }
+}
+
+pub fn query_campaign_pool(deps: Deps, env: Env, campaign_id: String) -> StdResult<Binary> {
+    let campaign_pool = CAMPAIGN_POOL.may_load(deps.storage, campaign_id)?;
+
+    match campaign_pool {
+        Some(pool) => to_json_binary(&CampaignResponse {
+            status: pool.status(env.block.time),
+            amount: pool.amount,
+            owner: pool.owner,
+            denom: pool.denom,
+            start_time: pool.start_time,
+            end_time: pool.end_time,
+            merkle_root: pool.merkle_root,
+            signer: pool.signer,
+        }),
+        None => Err(StdError::generic_err("Campaign does not exist")),
+    }
+}
+
+pub fn query_denoms(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let denoms = ALLOWED_DENOMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (denom, allowed) = item?;
+            Ok(DenomResponse {
+                denom,
+                min_deposit: allowed.min_deposit,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&ListDenomsResponse { denoms })
+}
+
+pub fn query_claimed(
+    deps: Deps,
+    _env: Env,
+    campaign_id: String,
+    address: String,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let claimed = CLAIMED
+        .may_load(deps.storage, (&campaign_id, &address))?
+        .unwrap_or_default();
+
+    to_json_binary(&ClaimedResponse { claimed })
+}
+
+pub fn query_merkle_claimed(
+    deps: Deps,
+    _env: Env,
+    campaign_id: String,
+    index: u32,
+) -> StdResult<Binary> {
+    let claimed = MERKLE_CLAIMED
+        .may_load(deps.storage, (&campaign_id, index / 64))?
+        .unwrap_or_default();
+
+    to_json_binary(&MerkleClaimedResponse {
+        claimed: claimed & (1u64 << (index % 64)) != 0,
+    })
+}
+
+pub fn query_signing_domain(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_json_binary(&SigningDomainResponse {
+        domain: signing_domain(&env),
+        legacy_signatures: LEGACY_SIGNATURES
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        hrp: HRP.load(deps.storage)?,
+    })
+}
+
+pub fn query_signer_keys(deps: Deps, env: Env) -> StdResult<Binary> {
+    let mut keys = vec![SignerKeyResponse {
+        pubkey: PUBKEY.load(deps.storage)?,
+        expires_at: None,
+    }];
+    if let Some(previous) = PREVIOUS_PUBKEY.may_load(deps.storage)? {
+        if env.block.time < previous.expires_at {
+            keys.push(SignerKeyResponse {
+                pubkey: previous.pubkey,
+                expires_at: Some(previous.expires_at),
+            });
+        }
+    }
+
+    let cosigners = SIGNERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(key, key_type)| SignerKey {
+                key_type,
+                key: Binary(key),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&SignerKeysResponse { keys, cosigners })
+}
+
+pub fn query_approval_policy(deps: Deps, _env: Env) -> StdResult<Binary> {
+    let thresholds = APPROVAL_THRESHOLDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, threshold)| ApprovalThresholdResponse { denom, threshold }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&ApprovalPolicyResponse {
+        required_signatures: REQUIRED_SIGNATURES.may_load(deps.storage)?.unwrap_or(1),
+        thresholds,
+    })
+}
+
+pub fn query_nonces(
+    deps: Deps,
+    _env: Env,
+    campaign_id: String,
+    sender: String,
+    nonces: Vec<u64>,
+) -> StdResult<Binary> {
+    if nonces.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "Cannot query more than {} nonces at once",
+            MAX_LIMIT
+        )));
+    }
+
+    let sender = deps.api.addr_validate(&sender)?;
+    let nonces = nonces
+        .into_iter()
+        .map(|nonce| {
+            let status = nonce_status(deps.storage, &campaign_id, &sender, nonce)?;
+            Ok(NonceResponse { nonce, status })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&NoncesResponse { nonces })
+}
+
+/// Compares the campaigns of a page and the allocated totals with the balance of the contract
+pub fn query_solvency(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let (totals, next_start_after) = campaign_totals(deps.storage, start_after, Some(limit))?;
+
+    let denoms = totals
+        .into_iter()
+        .map(|(denom_id, (denom, campaigns))| {
+            let allocated = ALLOCATED
+                .may_load(deps.storage, &denom_id)?
+                .unwrap_or_default();
+            let balance = denom_balance(deps, &env, &denom)?;
+            // campaigns missing from the allocated total are owed all the same
+            let owed = allocated.max(campaigns);
+            Ok(DenomSolvencyResponse {
+                denom: denom_id,
+                campaigns,
+                allocated,
+                balance,
+                surplus: balance.saturating_sub(owed),
+                deficit: owed.saturating_sub(balance),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&SolvencyResponse {
+        denoms,
+        next_start_after,
+    })
+}
+
+pub fn query_admin(deps: Deps, _env: Env) -> StdResult<Binary> {
+    to_json_binary(&AdminResponse {
+        admin: deps.api.addr_humanize(&ADMIN.load(deps.storage)?)?,
+        pending_admin: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_roles(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let roles = ROLES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(address, roles)| RoleAssignmentResponse { address, roles }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&ListRolesResponse { roles })
+}
@@ -1,36 +1,298 @@
 use bech32::{ToBase32, Variant};
-use cosmwasm_std::{ensure, to_json_string, Binary, CanonicalAddr, Deps, StdError};
+use cosmwasm_std::{
+    ensure, to_json_string, Addr, Binary, CanonicalAddr, Deps, Env, HexBinary, Order, StdError,
+    Uint128,
+};
 use k256::sha2::{Digest, Sha256};
 use ripemd::Ripemd160;
+use serde::Serialize;
+use sha3::Keccak256;
 
-use crate::{msg::SignedData, state::PUBKEY};
+use crate::msg::{DomainSigned, SigningDomain};
+use crate::state::{KeyType, SignerKey, HRP, LEGACY_SIGNATURES, PREVIOUS_PUBKEY, PUBKEY, SIGNERS};
 
-// wallet address part before 1abc12345..., eg secret for secret1ac94abc....
-const HRP: &str = "CHNGME";
+/// Version of the signed document, bumped whenever its layout changes
+pub const SIGNING_VERSION: u8 = 1;
 
-pub fn verify_arbitrary(deps: Deps, data: &SignedData, signature: &[u8]) -> Result<(), StdError> {
+pub fn signing_domain(env: &Env) -> SigningDomain {
+    SigningDomain {
+        version: SIGNING_VERSION,
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.clone(),
+    }
+}
+
+/// Accepts compressed and uncompressed secp256k1 keys
+pub fn validate_pubkey(pubkey: &Binary) -> Result<(), StdError> {
+    ensure!(
+        pubkey.len() == 33 || pubkey.len() == 65,
+        StdError::generic_err("Invalid public key")
+    );
+
+    Ok(())
+}
+
+pub fn validate_signer_key(signer: &SignerKey) -> Result<(), StdError> {
+    match signer.key_type {
+        KeyType::Secp256k1 => validate_pubkey(&signer.key),
+        KeyType::Ethereum => {
+            ensure!(
+                signer.key.len() == 20,
+                StdError::generic_err("Invalid Ethereum address")
+            );
+            Ok(())
+        }
+        KeyType::Ed25519 => {
+            ensure!(
+                signer.key.len() == 32,
+                StdError::generic_err("Invalid public key")
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Returns the keys claims can currently be signed with, grouped per signer.
+/// The backend key comes first, together with the replaced key during its grace period,
+/// followed by the registered co-signers.
+pub fn active_signers(deps: Deps, env: &Env) -> Result<Vec<Vec<SignerKey>>, StdError> {
     let key = PUBKEY.load(deps.storage);
     ensure!(key.is_ok(), StdError::generic_err("Key Not Set"));
-    let key = key.unwrap();
 
-    let digest = Sha256::new_with_prefix(generate_amino_transaction_string(
-        pubkey_to_account(&key, HRP).as_str(),
-        to_json_string(&data)?.as_str(),
-    ))
-    .finalize();
+    let mut keys = vec![key.unwrap()];
+    if let Some(previous) = PREVIOUS_PUBKEY.may_load(deps.storage)? {
+        if env.block.time < previous.expires_at {
+            keys.push(previous.pubkey);
+        }
+    }
+
+    let mut signers = vec![keys
+        .into_iter()
+        .map(|key| SignerKey {
+            key_type: KeyType::Secp256k1,
+            key,
+        })
+        .collect()];
+    for cosigner in SIGNERS.range(deps.storage, None, None, Order::Ascending) {
+        let (key, key_type) = cosigner?;
+        signers.push(vec![SignerKey {
+            key_type,
+            key: Binary(key),
+        }]);
+    }
+
+    Ok(signers)
+}
+
+/// Returns the JSON document signers sign for `data` on this chain and contract
+pub fn signed_document<T: Serialize>(env: &Env, data: &T) -> Result<String, StdError> {
+    to_json_string(&DomainSigned {
+        domain: signing_domain(env),
+        data,
+    })
+}
+
+/// Verifies that `data` was signed for this chain and contract by `required` of the given signers,
+/// each signer being the list of keys it can sign with.
+/// Signatures over the bare data are accepted as well while legacy signatures are enabled.
+pub fn verify_arbitrary<T: Serialize>(
+    deps: Deps,
+    env: &Env,
+    data: &T,
+    signatures: &[Binary],
+    signers: Vec<Vec<SignerKey>>,
+    required: u32,
+) -> Result<(), StdError> {
+    let hrp = HRP.load(deps.storage)?;
+    let document = signed_document(env, data)?;
+    let legacy = match LEGACY_SIGNATURES
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        true => Some(to_json_string(data)?),
+        false => None,
+    };
+
+    // a key listed for several signers still approves only once
+    let mut approved: Vec<Binary> = vec![];
+    'signers: for keys in signers {
+        for key in keys {
+            if approved.contains(&key.key) {
+                continue;
+            }
+
+            for signature in signatures {
+                if verify_document(deps, &key, &hrp, &document, signature)? {
+                    approved.push(key.key);
+                    continue 'signers;
+                }
+
+                // legacy documents predate the other key types
+                if let (Some(legacy), KeyType::Secp256k1) = (&legacy, &key.key_type) {
+                    if verify_document(deps, &key, &hrp, legacy, signature)? {
+                        approved.push(key.key);
+                        continue 'signers;
+                    }
+                }
+            }
+        }
+    }
+
+    let approvals = approved.len() as u32;
+
+    ensure!(approvals > 0, StdError::generic_err("Invalid signature"));
+    ensure!(
+        approvals >= required,
+        StdError::generic_err(format!(
+            "Claim requires signatures from {} distinct signers",
+            required
+        ))
+    );
+
+    Ok(())
+}
+
+fn verify_document(
+    deps: Deps,
+    signer: &SignerKey,
+    hrp: &str,
+    document: &str,
+    signature: &[u8],
+) -> Result<bool, StdError> {
+    match signer.key_type {
+        KeyType::Secp256k1 => {
+            // signatures of other schemes can be sent along in the same claim
+            if signature.len() != 64 {
+                return Ok(false);
+            }
+
+            // ADR-036 requires an empty chain id in the amino document,
+            // the chain is bound by the signing domain inside the data instead
+            let account = pubkey_to_account(&signer.key, hrp);
+            let digest =
+                Sha256::new_with_prefix(generate_amino_transaction_string(&account, document))
+                    .finalize();
+
+            Ok(deps.api.secp256k1_verify(&digest, signature, &signer.key)?)
+        }
+        KeyType::Ethereum => verify_eip191(deps, &signer.key, document.as_bytes(), signature),
+        KeyType::Ed25519 => {
+            if signature.len() != 64 {
+                return Ok(false);
+            }
+
+            Ok(deps
+                .api
+                .ed25519_verify(document.as_bytes(), signature, &signer.key)?)
+        }
+    }
+}
+
+/// Checks an EIP-191 `personal_sign` signature of `message` by the Ethereum `address`
+pub fn verify_eip191(
+    deps: Deps,
+    address: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, StdError> {
+    // r || s || v, with v either 27/28 or 0/1
+    if signature.len() != 65 {
+        return Ok(false);
+    }
+    let recovery_param = match signature[64] {
+        27 | 28 => signature[64] - 27,
+        0 | 1 => signature[64],
+        _ => return Ok(false),
+    };
+
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()));
+    hasher.update(message);
+    let hash = hasher.finalize();
+
+    match deps
+        .api
+        .secp256k1_recover_pubkey(&hash, &signature[..64], recovery_param)
+    {
+        Ok(pubkey) => Ok(ethereum_address(&pubkey) == address),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Last 20 bytes of the keccak256 hash of an uncompressed public key
+pub fn ethereum_address(pubkey: &[u8]) -> [u8; 20] {
+    let hash = Keccak256::new_with_prefix(&pubkey[1..]).finalize();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Checks that `(address, amount)` is the leaf at `index` of the tree with the given root.
+///
+/// Leaves are `sha256("{address}:{amount}")` and parents `sha256(left || right)`,
+/// the bits of `index` decide on which side the sibling at each level is.
+pub fn verify_merkle_proof(
+    root: &HexBinary,
+    address: &Addr,
+    amount: Uint128,
+    index: u32,
+    proof: &[HexBinary],
+) -> Result<(), StdError> {
+    // an index wider than the tree would reuse the same path for another bitmap slot,
+    // and a u32 index cannot address a tree deeper than 32 levels
+    ensure!(
+        proof.len() == 32 || proof.len() < 32 && index >> proof.len() == 0,
+        StdError::generic_err("Leaf index out of range")
+    );
+
+    let mut hash = sha_256(format!("{}:{}", address, amount).as_bytes());
+    for (level, sibling) in proof.iter().enumerate() {
+        ensure!(sibling.len() == 32, StdError::generic_err("Invalid proof"));
+        let mut hasher = Sha256::new();
+        if index >> level & 1 == 0 {
+            hasher.update(hash);
+            hasher.update(sibling.as_slice());
+        } else {
+            hasher.update(sibling.as_slice());
+            hasher.update(hash);
+        }
+        hash = hasher.finalize().into();
+    }
 
-    deps.api.secp256k1_verify(&digest, signature, &key)?;
+    ensure!(
+        hash.as_slice() == root.as_slice(),
+        StdError::generic_err("Invalid Merkle proof")
+    );
 
     Ok(())
 }
 
-fn generate_amino_transaction_string(signer: &str, data: &str) -> String {
+pub(crate) fn generate_amino_transaction_string(signer: &str, data: &str) -> String {
     format!(
         "{{\"account_number\":\"0\",\"chain_id\":\"\",\"fee\":{{\"amount\":[],\"gas\":\"0\"}},\"memo\":\"\",\"msgs\":[{{\"type\":\"sign/MsgSignData\",\"value\":{{\"data\":\"{}\",\"signer\":\"{}\"}}}}],\"sequence\":\"0\"}}",
         data, signer
     )
 }
 
+/// Returns the wallet address part before 1abc12345..., eg secret for secret1ac94abc....
+pub fn address_prefix(address: &str) -> Result<String, StdError> {
+    bech32::decode(address).map(|(hrp, _, _)| hrp).map_err(|_| {
+        StdError::generic_err(
+            "Cannot derive the address prefix from the contract address, pass hrp explicitly",
+        )
+    })
+}
+
+pub fn validate_hrp(hrp: &str) -> Result<(), StdError> {
+    ensure!(
+        hrp.to_lowercase() == hrp
+            && bech32::encode(hrp, [0u8].to_base32(), Variant::Bech32).is_ok(),
+        StdError::generic_err("Invalid address prefix")
+    );
+
+    Ok(())
+}
+
 pub fn pubkey_to_account(pubkey: &Binary, hrp: &str) -> String {
     let base32_addr = pubkey_to_canonical(pubkey).0.as_slice().to_base32();
     let account: String = bech32::encode(hrp, base32_addr, Variant::Bech32).unwrap();
@@ -46,9 +308,5 @@ fn pubkey_to_canonical(pubkey: &Binary) -> CanonicalAddr {
 fn sha_256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(data);
-    let hash = hasher.finalize();
-
-    let mut result = [0u8; 32];
-    result.copy_from_slice(hash.as_slice());
-    result
+    hasher.finalize().into()
 }
@@ -1,10 +1,16 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, HexBinary, Timestamp, Uint128};
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw_utils::Expiration;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{CampaignStatus, KeyType, NonceStatus, PendingAdmin, Role, SignerKey};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
     pub pubkey: Binary,
+    /// Bech32 prefix of the signer address, derived from the contract address if not set
+    pub hrp: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -25,21 +31,94 @@ pub struct UserRewardResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    EditAdmin {
-        new_admin: Addr,
+    /// Proposes a new admin, who takes over once they accept
+    ProposeAdmin {
+        new_admin: String,
+        /// The proposal cannot be accepted from then on, never expires if not set
+        expires_at: Option<Expiration>,
+    },
+    AcceptAdmin {},
+    /// Lets `address` perform the operations of `role`
+    GrantRole {
+        address: String,
+        role: Role,
     },
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
+    CancelAdminProposal {},
     Deposit {
         campaign_id: String,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+        /// Key that signs the claims of this campaign instead of the global signers
+        signer: Option<Binary>,
+        /// Scheme of `signer`, secp256k1 if not set
+        signer_type: Option<KeyType>,
     },
     Claim {
         campaign_id: String,
         amount: Uint128,
         denom: String,
-        nonce: String,
+        /// Sequential per campaign and claimer
+        nonce: u64,
+        /// Pays the claim out to another address, the signature has to commit to it
+        recipient: Option<String>,
+        signature: Binary,
+        /// Signatures of further signers, needed for claims above the approval threshold
+        cosignatures: Option<Vec<Binary>>,
+        /// Has to match the expiry the voucher was signed with
+        expires_at: Option<Expiration>,
+    },
+    ClaimFor {
+        campaign_id: String,
+        amount: Uint128,
+        denom: String,
+        nonce: u64,
+        recipient: String,
+        relayer_fee: Option<Uint128>,
         signature: Binary,
+        /// Signatures of further signers, needed for claims above the approval threshold
+        cosignatures: Option<Vec<Binary>>,
+        /// Has to match the expiry the voucher was signed with
+        expires_at: Option<Expiration>,
+    },
+    ClaimBatch {
+        claims: Vec<ClaimItem>,
+    },
+    ClaimCumulative {
+        campaign_id: String,
+        denom: String,
+        total: Uint128,
+        signature: Binary,
+        /// Signatures of further signers, needed for claims above the approval threshold
+        cosignatures: Option<Vec<Binary>>,
+        /// Has to match the expiry the voucher was signed with
+        expires_at: Option<Expiration>,
+    },
+    RegisterMerkleRoot {
+        campaign_id: String,
+        merkle_root: HexBinary,
+    },
+    ClaimMerkle {
+        campaign_id: String,
+        amount: Uint128,
+        index: u32,
+        proof: Vec<HexBinary>,
     },
+    /// Withdraws funds that are not allocated to any campaign
     Withdraw {
         amount: Uint128,
+        denom: Option<String>,
+        token: Option<String>,
+    },
+    /// Withdraws funds including those allocated to campaigns, emits an `emergency_withdraw` event
+    EmergencyWithdraw {
+        amount: Uint128,
+        denom: Option<String>,
+        token: Option<String>,
+        reason: String,
     },
     Cancel {
         campaign_id: String,
@@ -48,19 +127,280 @@ pub enum ExecuteMsg {
         campaign_id: String,
         amount: Uint128,
     },
+    Receive(Cw20ReceiveMsg),
+    AddDenom {
+        denom: String,
+        min_deposit: Option<Uint128>,
+    },
+    RemoveDenom {
+        denom: String,
+    },
+    SweepExpired {
+        campaign_ids: Vec<String>,
+    },
+    SetLegacySignatures {
+        enabled: bool,
+    },
+    SetHrp {
+        hrp: String,
+    },
+    RotateSigner {
+        pubkey: Binary,
+        /// Seconds the replaced key stays valid for, so vouchers already handed out can be claimed
+        grace_period: Option<u64>,
+    },
+    AddSigner {
+        /// Public key, or the 20 byte address of Ethereum signers
+        pubkey: Binary,
+        /// secp256k1 if not set
+        key_type: Option<KeyType>,
+    },
+    RemoveSigner {
+        pubkey: Binary,
+    },
+    SetRequiredSignatures {
+        required: u32,
+    },
+    /// Claims of more than `threshold` in `denom` need the required number of signatures
+    SetApprovalThreshold {
+        denom: String,
+        threshold: Option<Uint128>,
+    },
+    /// Invalidates outstanding vouchers of `sender`, callable by the admin and the signer accounts
+    RevokeNonces {
+        campaign_id: String,
+        sender: String,
+        nonces: Vec<u64>,
+    },
+    /// Removes the nonces of a closed campaign, at most `limit` storage entries per call.
+    /// Without a campaign id the nonces of the 0.1.2 release are removed.
+    PruneNonces {
+        campaign_id: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// A single signed claim of a `ClaimBatch`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClaimItem {
+    pub campaign_id: String,
+    pub amount: Uint128,
+    pub denom: String,
+    pub nonce: u64,
+    pub signature: Binary,
+    pub cosignatures: Option<Vec<Binary>>,
+    pub expires_at: Option<Expiration>,
+}
+
+/// Messages accepted inside `Cw20ReceiveMsg.msg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Deposit {
+        campaign_id: String,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+        /// Key that signs the claims of this campaign instead of the global signers
+        signer: Option<Binary>,
+        /// Scheme of `signer`, secp256k1 if not set
+        signer_type: Option<KeyType>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    GetCpool { campaign_id: String },
+    GetCpool {
+        campaign_id: String,
+    },
+    ListDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Claimed {
+        campaign_id: String,
+        address: String,
+    },
+    MerkleClaimed {
+        campaign_id: String,
+        index: u32,
+    },
+    SigningDomain {},
+    SignerKeys {},
+    ApprovalPolicy {},
+    Nonces {
+        campaign_id: String,
+        sender: String,
+        nonces: Vec<u64>,
+    },
+    Admin {},
+    ListRoles {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Pages through the campaigns, pass `next_start_after` of the response to continue
+    Solvency {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CampaignResponse {
+    pub amount: Uint128,
+    pub owner: Addr,
+    pub denom: Denom,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    pub merkle_root: Option<HexBinary>,
+    pub signer: Option<SignerKey>,
+    pub status: CampaignStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClaimedResponse {
+    pub claimed: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MerkleClaimedResponse {
+    pub claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SigningDomainResponse {
+    pub domain: SigningDomain,
+    pub legacy_signatures: bool,
+    pub hrp: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SignerKeyResponse {
+    pub pubkey: Binary,
+    /// Set for the replaced key during its grace period
+    pub expires_at: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SignerKeysResponse {
+    pub keys: Vec<SignerKeyResponse>,
+    pub cosigners: Vec<SignerKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApprovalThresholdResponse {
+    pub denom: String,
+    pub threshold: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApprovalPolicyResponse {
+    pub required_signatures: u32,
+    pub thresholds: Vec<ApprovalThresholdResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NonceResponse {
+    pub nonce: u64,
+    pub status: NonceStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NoncesResponse {
+    pub nonces: Vec<NonceResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AdminResponse {
+    pub admin: Addr,
+    pub pending_admin: Option<PendingAdmin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RoleAssignmentResponse {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ListRolesResponse {
+    pub roles: Vec<RoleAssignmentResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DenomSolvencyResponse {
+    pub denom: String,
+    /// Sum of the campaigns of this page
+    pub campaigns: Uint128,
+    /// Sum of all campaigns
+    pub allocated: Uint128,
+    pub balance: Uint128,
+    /// Balance beyond the larger of `campaigns` and `allocated`
+    pub surplus: Uint128,
+    /// Part of the larger of `campaigns` and `allocated` the balance does not cover
+    pub deficit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolvencyResponse {
+    pub denoms: Vec<DenomSolvencyResponse>,
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DenomResponse {
+    pub denom: String,
+    pub min_deposit: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ListDenomsResponse {
+    pub denoms: Vec<DenomResponse>,
 }
 
 #[cw_serde]
 pub struct SignedData {
     pub campaign_id: String,
-    pub nonce: String,
+    pub nonce: u64,
     pub denom: String,
     pub amount: Uint128,
     pub sender: Addr,
+    // optional fields are left out of the signed document when unset,
+    // so vouchers signed before they existed still verify
+    /// Address the claim is paid to, set for vouchers that can be relayed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<Addr>,
+    /// Part of the amount paid to whoever relays the claim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relayer_fee: Option<Uint128>,
+    /// Block height or time after which the voucher can no longer be claimed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Expiration>,
+}
+
+/// Deployment a signature is valid for, signed next to the claim data
+#[cw_serde]
+pub struct SigningDomain {
+    pub version: u8,
+    pub chain_id: String,
+    pub contract: Addr,
+}
+
+/// Document that is actually signed: `{"domain": {...}, "data": {...}}`
+#[derive(Serialize)]
+pub struct DomainSigned<'a, T: Serialize> {
+    pub domain: SigningDomain,
+    pub data: &'a T,
+}
+
+/// Attests that `sender` has earned `total` from the campaign so far
+#[cw_serde]
+pub struct SignedCumulativeData {
+    pub campaign_id: String,
+    pub denom: String,
+    pub total: Uint128,
+    pub sender: Addr,
+    /// Block height or time after which the voucher can no longer be claimed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Expiration>,
 }
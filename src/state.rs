@@ -1,16 +1,160 @@
-use cosmwasm_std::{Addr, Binary, CanonicalAddr, Uint128};
+use cosmwasm_std::{Addr, Binary, CanonicalAddr, Empty, HexBinary, Timestamp, Uint128};
+use cw20::Denom;
 use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 pub const ADMIN: Item<CanonicalAddr> = Item::new("admin");
+/// Admin proposed by the current one, waiting for them to accept
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+/// Roles granted by the admin, who holds all of them implicitly
+pub const ROLES: Map<&Addr, Vec<Role>> = Map::new("roles");
 pub const PUBKEY: Item<Binary> = Item::new("key");
+/// Key replaced by the last rotation, still accepted until its grace period ends
+pub const PREVIOUS_PUBKEY: Item<GraceKey> = Item::new("previous_key");
+/// Co-signer keys registered next to `PUBKEY`, each one counts as a distinct signer
+pub const SIGNERS: Map<&[u8], KeyType> = Map::new("signers");
+/// Distinct signers needed for claims above the approval threshold of their denom
+pub const REQUIRED_SIGNATURES: Item<u32> = Item::new("required_signatures");
+/// Claim amount per denom above which the required signatures are enforced
+pub const APPROVAL_THRESHOLDS: Map<&str, Uint128> = Map::new("approval_thresholds");
+/// Bech32 prefix of the chain, used for the ADR-036 signer address
+pub const HRP: Item<String> = Item::new("hrp");
+/// Whether signatures without a signing domain are still accepted
+pub const LEGACY_SIGNATURES: Item<bool> = Item::new("legacy_signatures");
+/// Denoms campaigns can be funded with, keyed by native denom or CW20 token address
+pub const ALLOWED_DENOMS: Map<&str, AllowedDenom> = Map::new("allowed_denoms");
 
-pub const NONCES: Map<&str, bool> = Map::new("nonces");
+/// Bitmap of claimed nonces per (campaign, claimer), 64 nonces per word
+pub const NONCES: Map<(&str, &Addr, u64), u64> = Map::new("nonce_bitmap");
+/// Bitmap of revoked nonces per (campaign, claimer), 64 nonces per word
+pub const REVOKED_NONCES: Map<(&str, &Addr, u64), u64> = Map::new("revoked_nonces");
+/// Global string nonces of the 0.1.2 release, deleted in pages by prune_nonces
+pub const LEGACY_NONCES: Map<&str, bool> = Map::new("nonces");
+/// Campaigns removed from the pool by a cancel, a sweep or their last claim,
+/// only their nonces can be pruned
+pub const REMOVED_CAMPAIGNS: Map<&str, Empty> = Map::new("removed_campaigns");
+/// Campaigns whose nonces were pruned, their ids cannot be funded again
+pub const CLOSED_CAMPAIGNS: Map<&str, Empty> = Map::new("closed_campaigns");
 pub const CAMPAIGN_POOL: Map<String, Campaign> = Map::new("campaign_pool");
+/// `CAMPAIGN_POOL` as read by migrations, which rewrite campaigns stored without a denom
+pub const LEGACY_CAMPAIGN_POOL: Map<String, LegacyCampaign> = Map::new("campaign_pool");
+/// Sum of the campaign amounts per denom id, only the balance above it can be withdrawn
+pub const ALLOCATED: Map<&str, Uint128> = Map::new("allocated");
+/// Cumulative amount paid out per (campaign, user) by cumulative claims
+pub const CLAIMED: Map<(&str, &Addr), Uint128> = Map::new("claimed");
+/// Bitmap of claimed Merkle leaves per campaign, 64 leaf indexes per word
+pub const MERKLE_CLAIMED: Map<(&str, u32), u64> = Map::new("merkle_claimed");
+
+/// Signature scheme of a signer key
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    /// secp256k1 public key signing ADR-036 documents
+    #[default]
+    Secp256k1,
+    /// Ethereum address signing EIP-191 `personal_sign` messages
+    Ethereum,
+    /// ed25519 public key signing the JSON document as is
+    Ed25519,
+}
+
+/// Operations the admin can delegate to other addresses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Withdraws unallocated funds, emergency withdrawals are left to the admin
+    Treasurer,
+    /// Adjusts campaign pools with `set_cpool`
+    PoolManager,
+    /// Manages signer keys, signature settings and revokes nonces
+    SignerManager,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Treasurer => write!(f, "treasurer"),
+            Role::PoolManager => write!(f, "pool_manager"),
+            Role::SignerManager => write!(f, "signer_manager"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires_at: Option<Expiration>,
+}
+
+/// Status of a voucher nonce
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceStatus {
+    Unused,
+    Claimed,
+    /// Invalidated before the voucher was claimed
+    Revoked,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignerKey {
+    pub key_type: KeyType,
+    /// Public key, or the 20 byte address of Ethereum signers
+    pub key: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GraceKey {
+    pub pubkey: Binary,
+    pub expires_at: Timestamp,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Campaign {
     pub amount: Uint128,
     pub owner: Addr,
+    /// Native denom or CW20 token contract the campaign was funded with
+    pub denom: Denom,
+    /// Claims are accepted from this time on, immediately if not set
+    pub start_time: Option<Timestamp>,
+    /// Claims are rejected from this time on, never if not set
+    pub end_time: Option<Timestamp>,
+    /// Set for airdrop campaigns, which can only be claimed with a Merkle proof
+    pub merkle_root: Option<HexBinary>,
+    /// Key of the campaign owner that signs claims instead of the global signers
+    pub signer: Option<SignerKey>,
+}
+
+/// Campaign in any stored format, those saved by the 0.1.2 release have no denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LegacyCampaign {
+    pub amount: Uint128,
+    pub owner: Addr,
+    pub denom: Option<Denom>,
+}
+
+impl Campaign {
+    pub fn status(&self, now: Timestamp) -> CampaignStatus {
+        match (self.start_time, self.end_time) {
+            (Some(start_time), _) if now < start_time => CampaignStatus::Pending,
+            (_, Some(end_time)) if now >= end_time => CampaignStatus::Ended,
+            _ => CampaignStatus::Active,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignStatus {
+    Pending,
+    Active,
+    Ended,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowedDenom {
+    pub min_deposit: Option<Uint128>,
 }
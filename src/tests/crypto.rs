@@ -0,0 +1,93 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{Addr, HexBinary, StdError, Uint128};
+use k256::ecdsa::SigningKey;
+
+use crate::crypto::{ethereum_address, verify_arbitrary, verify_eip191};
+use crate::msg::SignedData;
+use crate::state::{KeyType, SignerKey, HRP};
+use crate::tests::{sign, signer_pubkey};
+
+// `web3.eth.accounts.sign("Some data", PRIVATE_KEY)` from the web3.js documentation
+const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const ADDRESS: &str = "2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+const SIGNATURE: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+#[test]
+fn test_ethereum_address() {
+    let secret = HexBinary::from_hex(PRIVATE_KEY).unwrap();
+    let key = SigningKey::from_slice(&secret).unwrap();
+    let pubkey = key.verifying_key().to_encoded_point(false);
+
+    assert_eq!(
+        HexBinary::from(ethereum_address(pubkey.as_bytes()).to_vec()).to_hex(),
+        ADDRESS
+    );
+}
+
+#[test]
+fn test_verify_eip191() {
+    let deps = mock_dependencies();
+    let address = HexBinary::from_hex(ADDRESS).unwrap();
+    let mut signature = HexBinary::from_hex(SIGNATURE).unwrap().to_vec();
+
+    assert!(verify_eip191(deps.as_ref(), &address, b"Some data", &signature).unwrap());
+    assert!(!verify_eip191(deps.as_ref(), &address, b"Other data", &signature).unwrap());
+    assert!(!verify_eip191(deps.as_ref(), &[0u8; 20], b"Some data", &signature).unwrap());
+
+    // v can also be given as the bare recovery id
+    signature[64] -= 27;
+    assert!(verify_eip191(deps.as_ref(), &address, b"Some data", &signature).unwrap());
+
+    signature[64] = 5;
+    assert!(!verify_eip191(deps.as_ref(), &address, b"Some data", &signature).unwrap());
+    assert!(!verify_eip191(deps.as_ref(), &address, b"Some data", &signature[..64]).unwrap());
+}
+
+#[test]
+fn test_verify_arbitrary_counts_keys_once() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    HRP.save(&mut deps.storage, &"archway".to_string()).unwrap();
+
+    let data = SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce: 1,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    };
+    let signer = SignerKey {
+        key_type: KeyType::Secp256k1,
+        key: signer_pubkey(),
+    };
+    let signature = sign(&data);
+
+    // the same key listed for two signers, signing twice
+    let res = verify_arbitrary(
+        deps.as_ref(),
+        &env,
+        &data,
+        &[signature.clone(), signature.clone()],
+        vec![vec![signer.clone()], vec![signer.clone()]],
+        2,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Claim requires signatures from 2 distinct signers"
+        ))
+    );
+
+    verify_arbitrary(
+        deps.as_ref(),
+        &env,
+        &data,
+        &[signature],
+        vec![vec![signer.clone()], vec![signer]],
+        1,
+    )
+    .unwrap();
+}
@@ -0,0 +1,180 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::StdError;
+use cw_utils::Expiration;
+
+use crate::contract::{accept_admin, add_denom, cancel_admin_proposal, instantiate, propose_admin};
+use crate::msg::InstantiateMsg;
+use crate::tests::signer_pubkey;
+
+#[test]
+fn test_transfer_admin() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new_admin", &[]),
+        "new_admin".to_string(),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can transfer the admin role"
+        ))
+    );
+
+    let res = accept_admin(deps.as_mut(), env.clone(), mock_info("new_admin", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("No admin transfer is pending"))
+    );
+
+    propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "new_admin".to_string(),
+        None,
+    )
+    .unwrap();
+
+    let res = accept_admin(deps.as_mut(), env.clone(), mock_info("other", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the proposed admin can accept the admin role"
+        ))
+    );
+
+    // the current admin keeps the role until the proposal is accepted
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uatom".to_string(),
+        None,
+    )
+    .unwrap();
+
+    accept_admin(deps.as_mut(), env.clone(), mock_info("new_admin", &[])).unwrap();
+
+    let res = add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uosmo".to_string(),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can manage denoms"
+        ))
+    );
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new_admin", &[]),
+        "uosmo".to_string(),
+        None,
+    )
+    .unwrap();
+
+    let res = accept_admin(deps.as_mut(), env.clone(), mock_info("new_admin", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("No admin transfer is pending"))
+    );
+}
+
+#[test]
+fn test_admin_proposal_expiry_and_cancel() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "new_admin".to_string(),
+        Some(Expiration::AtHeight(env.block.height)),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Proposal expiry must be in the future"
+        ))
+    );
+
+    let res = propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "New_Admin".to_string(),
+        None,
+    );
+    assert!(res.is_err());
+
+    propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "new_admin".to_string(),
+        Some(Expiration::AtHeight(env.block.height + 10)),
+    )
+    .unwrap();
+
+    let mut later = env.clone();
+    later.block.height += 10;
+    let res = accept_admin(deps.as_mut(), later, mock_info("new_admin", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Admin proposal has expired"))
+    );
+
+    let res = cancel_admin_proposal(deps.as_mut(), env.clone(), mock_info("new_admin", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can transfer the admin role"
+        ))
+    );
+
+    cancel_admin_proposal(deps.as_mut(), env.clone(), mock_info("creator", &[])).unwrap();
+
+    let res = accept_admin(deps.as_mut(), env.clone(), mock_info("new_admin", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("No admin transfer is pending"))
+    );
+
+    let res = cancel_admin_proposal(deps.as_mut(), env.clone(), mock_info("creator", &[]));
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("No admin transfer is pending"))
+    );
+}
@@ -0,0 +1,190 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, Binary, StdError, Uint128};
+
+use crate::contract::{claim, deposit, instantiate};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::{KeyType, SignerKey, CAMPAIGN_POOL};
+use crate::tests::{pubkey_of, sign_with, signer_pubkey, OTHER_SECRET, SIGNER_SECRET};
+
+fn owner_signer() -> SignerKey {
+    SignerKey {
+        key_type: KeyType::Secp256k1,
+        key: pubkey_of(&OTHER_SECRET),
+    }
+}
+
+fn signed_data(campaign_id: &str, nonce: u64) -> SignedData {
+    SignedData {
+        campaign_id: campaign_id.to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_campaign_signer() {
+    let mut deps = mock_dependencies_with_balance(&coins(2000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &coins(1000, "")),
+        "own_campaign".to_string(),
+        None,
+        None,
+        Some(owner_signer()),
+    )
+    .unwrap();
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "global_campaign".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let campaign = CAMPAIGN_POOL
+        .load(&deps.storage, "own_campaign".to_string())
+        .unwrap();
+    assert_eq!(campaign.signer, Some(owner_signer()));
+
+    // the global key cannot sign for a campaign with its own signer
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "own_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data("own_campaign", 1)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "own_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign_with(&OTHER_SECRET, &signed_data("own_campaign", 2)),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // and the campaign signer only signs for its own campaign
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "global_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        sign_with(&OTHER_SECRET, &signed_data("global_campaign", 3)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "global_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        4,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data("global_campaign", 4)),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_campaign_signer_invalid() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &coins(1000, "")),
+        "own_campaign".to_string(),
+        None,
+        None,
+        Some(SignerKey {
+            key_type: KeyType::Secp256k1,
+            key: Binary(vec![2u8; 20]),
+        }),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid public key")));
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &coins(1000, "")),
+        "own_campaign".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &coins(1000, "")),
+        "own_campaign".to_string(),
+        None,
+        None,
+        Some(owner_signer()),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign signer can only be set when the campaign is created"
+        ))
+    );
+}
@@ -1,20 +1,15 @@
-use std::vec;
-
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
-use cosmwasm_std::{
-    coins, from_binary, from_json, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg,
-    DepsMut, Env, MessageInfo, StdError, SubMsg, Uint128,
-};
+use cosmwasm_std::{coins, to_json_binary, BankMsg, CosmosMsg, StdError, SubMsg};
 
-use crate::contract::{cancel, claim, deposit, instantiate, set_cpool, withdraw};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
-use crate::state::{Campaign, CAMPAIGN_POOL};
+use crate::contract::{cancel, deposit, instantiate};
+use crate::msg::InstantiateMsg;
+use crate::state::CAMPAIGN_POOL;
 
 #[test]
 fn test_cancel_as_contract_owner() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(100, ""));
     let env = mock_env();
 
     instantiate(
@@ -23,6 +18,7 @@ fn test_cancel_as_contract_owner() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -32,6 +28,9 @@ fn test_cancel_as_contract_owner() {
         env.clone(),
         mock_info("sender1", &coins(100, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -51,15 +50,12 @@ fn test_cancel_as_contract_owner() {
         }))]
     );
 
-    assert_eq!(
-        CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()),
-        false
-    );
+    assert!(!CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
 }
 
 #[test]
 fn test_cancel_as_campaign_owner() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(100, ""));
     let env = mock_env();
 
     instantiate(
@@ -68,6 +64,7 @@ fn test_cancel_as_campaign_owner() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -77,6 +74,9 @@ fn test_cancel_as_campaign_owner() {
         env.clone(),
         mock_info("sender1", &coins(100, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -96,50 +96,7 @@ fn test_cancel_as_campaign_owner() {
         }))]
     );
 
-    assert_eq!(
-        CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()),
-        false
-    );
-}
-
-#[test]
-fn test_cancel_non_refundable_campaign() {
-    let mut deps = mock_dependencies();
-    let env = mock_env();
-
-    instantiate(
-        deps.as_mut(),
-        env.clone(),
-        mock_info("creator", &[]),
-        InstantiateMsg {
-            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
-        },
-    )
-    .unwrap();
-
-    deposit(
-        deps.as_mut(),
-        env.clone(),
-        mock_info("sender1", &coins(100, "")),
-        "test_campaign_1".to_string(),
-    )
-    .unwrap();
-
-    let res = cancel(
-        deps.as_mut(),
-        env.clone(),
-        mock_info("creator", &[]),
-        "test_campaign_1".to_string(),
-    );
-
-    assert_eq!(
-        res,
-        Err(StdError::generic_err(
-            "Campaign was not set to be refundable"
-        ))
-    );
-
-    assert!(CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
+    assert!(!CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
 }
 
 #[test]
@@ -153,6 +110,7 @@ fn test_cancel_non_existent_campaign() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -166,8 +124,5 @@ fn test_cancel_non_existent_campaign() {
 
     assert_eq!(res, Err(StdError::generic_err("Campaign does not exist")));
 
-    assert_eq!(
-        CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()),
-        false
-    );
+    assert!(!CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
 }
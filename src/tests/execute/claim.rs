@@ -1,20 +1,179 @@
-use std::vec;
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, BankMsg, Binary, CosmosMsg, StdError, SubMsg, Uint128};
 
-use cosmwasm_std::testing::{
-    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
-};
-use cosmwasm_std::{
-    coins, from_binary, from_json, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg, DepsMut, Env,
-    MessageInfo, StdError, SubMsg, Uint128,
-};
+use crate::contract::{add_denom, claim, deposit, instantiate};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::CAMPAIGN_POOL;
+use crate::tests::{sign, signer_pubkey};
 
-use crate::contract::{cancel, claim, deposit, instantiate, set_cpool, withdraw};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
-use crate::state::{Campaign, CAMPAIGN_POOL};
+fn signed(campaign_id: &str, nonce: u64, amount: u128, sender: &str) -> Binary {
+    sign(&SignedData {
+        campaign_id: campaign_id.to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(amount),
+        sender: Addr::unchecked(sender),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    })
+}
 
 #[test]
 fn test_claim() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // claim from user1
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        signed("test_campaign_1", 1, 400, "user1"),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(400, ""),
+        }))]
+    );
+
+    let campaign = CAMPAIGN_POOL
+        .load(deps.as_ref().storage, "test_campaign_1".to_string())
+        .unwrap();
+    assert_eq!(campaign.amount, Uint128::new(600));
+
+    // the same nonce cannot be used twice
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        signed("test_campaign_1", 1, 400, "user1"),
+        None,
+        None,
+    );
+
+    assert_eq!(resp, Err(StdError::generic_err("Nonce has been used")));
+
+    // nonces are scoped per claimer
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user2", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        signed("test_campaign_1", 1, 400, "user2"),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_claim_invalid_signature() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // signature was issued for user1, not user2
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user2", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        signed("test_campaign_1", 1, 400, "user1"),
+        None,
+        None,
+    );
+
+    assert_eq!(resp, Err(StdError::generic_err("Invalid signature")));
+
+    // signature was issued for a smaller amount
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(500),
+        2,
+        None,
+        signed("test_campaign_1", 2, 400, "user1"),
+        None,
+        None,
+    );
+
+    assert_eq!(resp, Err(StdError::generic_err("Invalid signature")));
+}
+
+#[test]
+fn test_claim_not_enough_funds() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
     let env = mock_env();
 
     instantiate(
@@ -22,7 +181,8 @@ fn test_claim() {
         env.clone(),
         mock_info("creator", &[]),
         InstantiateMsg {
-            claim_reward_fee: Some(Uint128::new(999)),
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -32,28 +192,91 @@ fn test_claim() {
         env.clone(),
         mock_info("sender", &coins(1000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
-    // try to claim from user1 without enough claim fees
     let resp = claim(
         deps.as_mut(),
         env.clone(),
-        mock_info("user1", &coins(998, "")),
+        mock_info("user1", &[]),
         "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(2000),
+        1,
+        None,
+        signed("test_campaign_1", 1, 2000, "user1"),
+        None,
+        None,
     );
 
     assert_eq!(
         resp,
-        Err(StdError::generic_err("You must attach 999 to claim reward"))
+        Err(StdError::generic_err("Campaign does not have enough funds"))
     );
+}
+
+#[test]
+fn test_claim_campaign_denom() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uusdc"));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uusdc".to_string(),
+        None,
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "uusdc")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let data = SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce: 1,
+        denom: "uusdc".to_string(),
+        amount: Uint128::new(400),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    };
 
-    // try to claim from user1
     let resp = claim(
         deps.as_mut(),
         env.clone(),
-        mock_info("user1", &coins(999, "")),
+        mock_info("user1", &[]),
         "test_campaign_1".to_string(),
+        "uusdc".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        sign(&data),
+        None,
+        None,
     )
     .unwrap();
 
@@ -61,24 +284,112 @@ fn test_claim() {
         resp.messages,
         vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
             to_address: "user1".to_string(),
-            amount: coins(1000, ""),
+            amount: coins(400, "uusdc"),
         }))]
     );
 
-    // try to claim from user2 who doesn't exist
+    // a voucher signed for another denom is rejected
     let resp = claim(
         deps.as_mut(),
         env.clone(),
-        mock_info("user2", &coins(999, "")),
+        mock_info("user1", &[]),
         "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        2,
+        None,
+        signed("test_campaign_1", 2, 400, "user1"),
+        None,
+        None,
     );
 
-    assert_eq!(resp, Err(StdError::generic_err("User pool does not exist")));
+    assert_eq!(resp, Err(StdError::generic_err("Invalid denom")));
 }
 
 #[test]
-fn test_claim_zero_fee() {
-    let mut deps = mock_dependencies();
+fn test_claim_window() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let mut env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let start_time = env.block.time.plus_seconds(100);
+    let end_time = env.block.time.plus_seconds(200);
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        Some(start_time),
+        Some(end_time),
+        None,
+    )
+    .unwrap();
+
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        signed("test_campaign_1", 1, 100, "user1"),
+        None,
+        None,
+    );
+    assert_eq!(
+        resp,
+        Err(StdError::generic_err("Campaign has not started yet"))
+    );
+
+    env.block.time = start_time;
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        signed("test_campaign_1", 2, 100, "user1"),
+        None,
+        None,
+    )
+    .unwrap();
+
+    env.block.time = end_time;
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        signed("test_campaign_1", 3, 100, "user1"),
+        None,
+        None,
+    );
+    assert_eq!(resp, Err(StdError::generic_err("Campaign has ended")));
+}
+
+#[test]
+fn test_claim_to_recipient() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
     let env = mock_env();
 
     instantiate(
@@ -86,7 +397,8 @@ fn test_claim_zero_fee() {
         env.clone(),
         mock_info("creator", &[]),
         InstantiateMsg {
-            claim_reward_fee: Some(Uint128::new(0)),
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -96,23 +408,93 @@ fn test_claim_zero_fee() {
         env.clone(),
         mock_info("sender", &coins(1000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
-    // try to claim from user1
+    let signed_for = |nonce: u64, recipient: &str| {
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce,
+            denom: "".to_string(),
+            amount: Uint128::new(100),
+            sender: Addr::unchecked("user1"),
+            recipient: Some(Addr::unchecked(recipient)),
+            relayer_fee: None,
+            expires_at: None,
+        })
+    };
+
     let resp = claim(
         deps.as_mut(),
         env.clone(),
         mock_info("user1", &[]),
         "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        Some("cold_wallet".to_string()),
+        signed_for(1, "cold_wallet"),
+        None,
+        None,
     )
     .unwrap();
 
     assert_eq!(
         resp.messages,
         vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
-            to_address: "user1".to_string(),
-            amount: coins(1000, ""),
+            to_address: "cold_wallet".to_string(),
+            amount: coins(100, ""),
         }))]
     );
+
+    // the recipient cannot be swapped
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        Some("other_wallet".to_string()),
+        signed_for(2, "cold_wallet"),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // or dropped
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        signed_for(3, "cold_wallet"),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // and a voucher without a recipient cannot be redirected
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        4,
+        Some("cold_wallet".to_string()),
+        signed("test_campaign_1", 4, 100, "user1"),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
 }
@@ -0,0 +1,151 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, StdError, SubMsg, Uint128};
+
+use crate::contract::{add_denom, claim_batch, deposit, instantiate};
+use crate::msg::{ClaimItem, InstantiateMsg, SignedData};
+use crate::tests::{sign, signer_pubkey};
+
+fn item(campaign_id: &str, denom: &str, nonce: u64, amount: u128, sender: &str) -> ClaimItem {
+    ClaimItem {
+        campaign_id: campaign_id.to_string(),
+        amount: Uint128::new(amount),
+        denom: denom.to_string(),
+        nonce,
+        signature: sign(&SignedData {
+            campaign_id: campaign_id.to_string(),
+            nonce,
+            denom: denom.to_string(),
+            amount: Uint128::new(amount),
+            sender: Addr::unchecked(sender),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        cosignatures: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_claim_batch() {
+    let mut deps = mock_dependencies_with_balance(&[coin(2000, ""), coin(1000, "uusdc")]);
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uusdc".to_string(),
+        None,
+    )
+    .unwrap();
+
+    for (campaign_id, denom) in [
+        ("test_campaign_1", ""),
+        ("test_campaign_2", ""),
+        ("test_campaign_3", "uusdc"),
+    ] {
+        deposit(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("sender", &coins(1000, denom)),
+            campaign_id.to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    let resp = claim_batch(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        vec![
+            item("test_campaign_1", "", 1, 100, "user1"),
+            item("test_campaign_3", "uusdc", 2, 300, "user1"),
+            item("test_campaign_2", "", 3, 200, "user1"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        resp.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user1".to_string(),
+                amount: coins(300, ""),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user1".to_string(),
+                amount: coins(300, "uusdc"),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn test_claim_batch_invalid_item() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // the second item was signed for someone else
+    let res = claim_batch(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        vec![
+            item("test_campaign_1", "", 1, 100, "user1"),
+            item("test_campaign_1", "", 2, 100, "user2"),
+        ],
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // a nonce cannot be used twice within a batch
+    let res = claim_batch(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        vec![
+            item("test_campaign_1", "", 3, 100, "user1"),
+            item("test_campaign_1", "", 3, 100, "user1"),
+        ],
+    );
+    assert_eq!(res, Err(StdError::generic_err("Nonce has been used")));
+
+    let res = claim_batch(deps.as_mut(), env.clone(), mock_info("user1", &[]), vec![]);
+    assert_eq!(res, Err(StdError::generic_err("No claims were sent")));
+}
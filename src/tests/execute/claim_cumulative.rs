@@ -0,0 +1,150 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, Addr, BankMsg, Binary, CosmosMsg, StdError, SubMsg, Uint128};
+
+use crate::contract::{claim_cumulative, deposit, instantiate, query_claimed};
+use crate::msg::{ClaimedResponse, InstantiateMsg, SignedCumulativeData};
+use crate::tests::{sign, signer_pubkey};
+
+fn signed(campaign_id: &str, total: u128, sender: &str) -> Binary {
+    sign(&SignedCumulativeData {
+        campaign_id: campaign_id.to_string(),
+        denom: "".to_string(),
+        total: Uint128::new(total),
+        sender: Addr::unchecked(sender),
+        expires_at: None,
+    })
+}
+
+#[test]
+fn test_claim_cumulative() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let resp = claim_cumulative(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(300),
+        signed("test_campaign_1", 300, "user1"),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(300, ""),
+        }))]
+    );
+
+    // re-submitting the same attestation pays nothing
+    let resp = claim_cumulative(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(300),
+        signed("test_campaign_1", 300, "user1"),
+        None,
+        None,
+    );
+    assert_eq!(resp, Err(StdError::generic_err("Nothing to claim")));
+
+    // a newer total only pays out the difference
+    let resp = claim_cumulative(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(500),
+        signed("test_campaign_1", 500, "user1"),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(200, ""),
+        }))]
+    );
+
+    let res = query_claimed(
+        deps.as_ref(),
+        env.clone(),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+    )
+    .unwrap();
+    let claimed: ClaimedResponse = from_json(res).unwrap();
+    assert_eq!(claimed.claimed, Uint128::new(500));
+}
+
+#[test]
+fn test_claim_cumulative_invalid_signature() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let resp = claim_cumulative(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user2", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(300),
+        signed("test_campaign_1", 300, "user1"),
+        None,
+        None,
+    );
+    assert_eq!(resp, Err(StdError::generic_err("Invalid signature")));
+}
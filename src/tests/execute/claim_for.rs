@@ -0,0 +1,279 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{
+    coins, to_json_string, Addr, BankMsg, Binary, CosmosMsg, StdError, SubMsg, Uint128,
+};
+
+use crate::contract::{claim, claim_for, deposit, instantiate};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::tests::{sign, signer_pubkey};
+
+fn signed(nonce: u64, amount: u128, recipient: &str, relayer_fee: Option<u128>) -> Binary {
+    sign(&SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(amount),
+        sender: Addr::unchecked(recipient),
+        recipient: Some(Addr::unchecked(recipient)),
+        relayer_fee: relayer_fee.map(Uint128::new),
+        expires_at: None,
+    })
+}
+
+#[test]
+fn test_claim_for() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let resp = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        "user1".to_string(),
+        Some(Uint128::new(10)),
+        signed(1, 400, "user1", Some(10)),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resp.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user1".to_string(),
+                amount: coins(390, ""),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "relayer".to_string(),
+                amount: coins(10, ""),
+            })),
+        ]
+    );
+
+    // without a fee only the recipient is paid
+    let resp = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        "user1".to_string(),
+        None,
+        signed(2, 100, "user1", None),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(100, ""),
+        }))]
+    );
+}
+
+#[test]
+fn test_claim_for_invalid() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // the relayer cannot raise its own fee
+    let res = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        "user1".to_string(),
+        Some(Uint128::new(20)),
+        signed(1, 400, "user1", Some(10)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // or redirect the payout
+    let res = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        2,
+        "relayer".to_string(),
+        Some(Uint128::new(10)),
+        signed(2, 400, "user1", Some(10)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    let res = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        3,
+        "user1".to_string(),
+        Some(Uint128::new(401)),
+        signed(3, 400, "user1", Some(401)),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Relayer fee must be less than the claimed amount"
+        ))
+    );
+
+    let res = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        3,
+        "user1".to_string(),
+        Some(Uint128::new(400)),
+        signed(3, 400, "user1", Some(400)),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Relayer fee must be less than the claimed amount"
+        ))
+    );
+
+    // an ordinary voucher cannot be relayed
+    let voucher = sign(&SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce: 4,
+        denom: "".to_string(),
+        amount: Uint128::new(400),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    });
+    let res = claim_for(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        4,
+        "user1".to_string(),
+        None,
+        voucher.clone(),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // but it can still be claimed by the user
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        5,
+        None,
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 5,
+            denom: "".to_string(),
+            amount: Uint128::new(400),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_signed_data_without_optional_fields() {
+    // vouchers without recipient and fee have to keep the original signed format
+    let data = SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce: 1,
+        denom: "aconst".to_string(),
+        amount: Uint128::new(400),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    };
+    assert_eq!(
+        to_json_string(&data).unwrap(),
+        r#"{"campaign_id":"test_campaign_1","nonce":1,"denom":"aconst","amount":"400","sender":"user1"}"#
+    );
+}
@@ -0,0 +1,317 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{
+    coins, from_json, Addr, BankMsg, CosmosMsg, HexBinary, StdError, SubMsg, Uint128,
+};
+use k256::sha2::{Digest, Sha256};
+
+use crate::contract::{
+    claim, claim_merkle, deposit, instantiate, query_merkle_claimed, register_merkle_root,
+};
+use crate::msg::{InstantiateMsg, MerkleClaimedResponse, SignedData};
+use crate::state::CAMPAIGN_POOL;
+use crate::tests::{sign, signer_pubkey};
+
+const LEAVES: [(&str, u128); 4] = [
+    ("user1", 100),
+    ("user2", 200),
+    ("user3", 300),
+    ("user4", 400),
+];
+
+fn hash(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+fn parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hash(&[left.as_slice(), right.as_slice()].concat())
+}
+
+/// Builds the 4 leaf tree over LEAVES, returns the root and the proof of every leaf
+fn tree() -> (HexBinary, Vec<Vec<HexBinary>>) {
+    let leaves: Vec<[u8; 32]> = LEAVES
+        .iter()
+        .map(|(address, amount)| hash(format!("{}:{}", address, amount).as_bytes()))
+        .collect();
+    let left = parent(&leaves[0], &leaves[1]);
+    let right = parent(&leaves[2], &leaves[3]);
+    let root = parent(&left, &right);
+
+    let hex = |hash: &[u8; 32]| HexBinary::from(hash.as_slice());
+    let proofs = vec![
+        vec![hex(&leaves[1]), hex(&right)],
+        vec![hex(&leaves[0]), hex(&right)],
+        vec![hex(&leaves[3]), hex(&left)],
+        vec![hex(&leaves[2]), hex(&left)],
+    ];
+
+    (hex(&root), proofs)
+}
+
+#[test]
+fn test_claim_merkle() {
+    let mut deps = mock_dependencies_with_balance(&coins(1001, ""));
+    let env = mock_env();
+    let (root, proofs) = tree();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = register_merkle_root(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_sender", &[]),
+        "test_campaign_1".to_string(),
+        root.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only campaign owner can register a Merkle root"
+        ))
+    );
+
+    register_merkle_root(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_1".to_string(),
+        root.clone(),
+    )
+    .unwrap();
+
+    let res = register_merkle_root(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_1".to_string(),
+        root,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Merkle root is already registered"))
+    );
+
+    let resp = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user3", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(300),
+        2,
+        proofs[2].clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user3".to_string(),
+            amount: coins(300, ""),
+        }))]
+    );
+
+    let res =
+        query_merkle_claimed(deps.as_ref(), env.clone(), "test_campaign_1".to_string(), 2).unwrap();
+    let claimed: MerkleClaimedResponse = from_json(res).unwrap();
+    assert!(claimed.claimed);
+
+    let res =
+        query_merkle_claimed(deps.as_ref(), env.clone(), "test_campaign_1".to_string(), 1).unwrap();
+    let claimed: MerkleClaimedResponse = from_json(res).unwrap();
+    assert!(!claimed.claimed);
+
+    let res = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user3", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(300),
+        2,
+        proofs[2].clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Already claimed")));
+
+    // the same path with bits above the tree depth set maps to another bitmap slot
+    let res = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user3", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(300),
+        2 + 4,
+        proofs[2].clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Leaf index out of range")));
+
+    // the campaign is funded with exactly the sum of the leaves
+    for index in [0, 1, 3] {
+        let (address, amount) = LEAVES[index];
+        claim_merkle(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(address, &[]),
+            "test_campaign_1".to_string(),
+            Uint128::new(amount),
+            index as u32,
+            proofs[index].clone(),
+        )
+        .unwrap();
+    }
+    let campaign = CAMPAIGN_POOL
+        .load(&deps.storage, "test_campaign_1".to_string())
+        .unwrap();
+    assert_eq!(campaign.amount, Uint128::zero());
+    assert_eq!(campaign.owner, Addr::unchecked("sender"));
+
+    // the drained id stays with its owner and cannot be taken over with a new root
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("squatter", &coins(1, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let res = register_merkle_root(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("squatter", &[]),
+        "test_campaign_1".to_string(),
+        HexBinary::from([0u8; 32].as_slice()),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only campaign owner can register a Merkle root"
+        ))
+    );
+    let campaign = CAMPAIGN_POOL
+        .load(&deps.storage, "test_campaign_1".to_string())
+        .unwrap();
+    assert_eq!(campaign.owner, Addr::unchecked("sender"));
+    assert_eq!(campaign.amount, Uint128::one());
+}
+
+#[test]
+fn test_claim_merkle_invalid_proof() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+    let (root, proofs) = tree();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    register_merkle_root(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_1".to_string(),
+        root,
+    )
+    .unwrap();
+
+    // someone else's leaf
+    let res = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(300),
+        2,
+        proofs[2].clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid Merkle proof")));
+
+    // more than the leaf amount
+    let res = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(1000),
+        0,
+        proofs[0].clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid Merkle proof")));
+
+    // deeper than a u32 index can address
+    let res = claim_merkle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(100),
+        u32::MAX,
+        vec![proofs[0][0].clone(); 33],
+    );
+    assert_eq!(res, Err(StdError::generic_err("Leaf index out of range")));
+
+    // signed vouchers are not accepted for airdrop campaigns
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 1,
+            denom: "".to_string(),
+            amount: Uint128::new(100),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign can only be claimed with a Merkle proof"
+        ))
+    );
+}
@@ -0,0 +1,173 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+};
+use cosmwasm_std::{coins, to_json_binary, StdError, Uint128};
+
+use crate::contract::{add_denom, deposit, instantiate, remove_denom};
+use crate::msg::InstantiateMsg;
+use crate::state::{AllowedDenom, ALLOWED_DENOMS};
+
+#[test]
+fn test_add_denom() {
+    let mut deps = mock_dependencies_with_balance(&coins(500, "uusdc"));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "uusdc")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Denom uusdc is not allowed"))
+    );
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uusdc".to_string(),
+        Some(Uint128::new(500)),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ALLOWED_DENOMS.load(deps.as_ref().storage, "uusdc"),
+        Ok(AllowedDenom {
+            min_deposit: Some(Uint128::new(500))
+        })
+    );
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(499, "uusdc")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Deposit is below the minimum of 500 uusdc"
+        ))
+    );
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(500, "uusdc")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_remove_denom() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    remove_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "".to_string(),
+    )
+    .unwrap();
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Denom  is not allowed")));
+
+    let res = remove_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Denom is not in the allow-list"))
+    );
+}
+
+#[test]
+fn test_manage_denoms_unauthorized() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_creator", &[]),
+        "uusdc".to_string(),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can manage denoms"
+        ))
+    );
+
+    let res = remove_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_creator", &[]),
+        "".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can manage denoms"
+        ))
+    );
+}
@@ -1,20 +1,14 @@
-use std::vec;
-
-use cosmwasm_std::testing::{
-    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
-};
-use cosmwasm_std::{
-    coins, from_binary, from_json, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg,
-    DepsMut, Env, MessageInfo, StdError, SubMsg, Uint128,
-};
-
-use crate::contract::{cancel, claim, deposit, instantiate, set_cpool, withdraw};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, StdError, Uint128};
+use cw20::Denom;
+
+use crate::contract::{add_denom, deposit, instantiate};
+use crate::msg::InstantiateMsg;
 use crate::state::{Campaign, CAMPAIGN_POOL};
 
 #[test]
 fn test_deposit() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(2000000, ""));
     let env = mock_env();
 
     instantiate(
@@ -23,6 +17,7 @@ fn test_deposit() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -33,6 +28,9 @@ fn test_deposit() {
         env.clone(),
         mock_info("sender", &coins(1000000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -42,6 +40,11 @@ fn test_deposit() {
         Ok(Campaign {
             amount: Uint128::new(1000000),
             owner: Addr::unchecked("sender"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
         })
     );
 
@@ -51,6 +54,9 @@ fn test_deposit() {
         env.clone(),
         mock_info("sender2", &coins(1000000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -60,6 +66,175 @@ fn test_deposit() {
         Ok(Campaign {
             amount: Uint128::new(2000000),
             owner: Addr::unchecked("sender"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
         })
     );
 }
+
+#[test]
+fn test_deposit_any_denom() {
+    let mut deps = mock_dependencies_with_balance(&coins(
+        1000,
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CE",
+    ));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CE".to_string(),
+        None,
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            "sender",
+            &coins(1000, "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CE"),
+        ),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let campaign = CAMPAIGN_POOL.load(deps.as_ref().storage, "test_campaign_1".to_string());
+    assert_eq!(
+        campaign,
+        Ok(Campaign {
+            amount: Uint128::new(1000),
+            owner: Addr::unchecked("sender"),
+            denom: Denom::Native("ibc/27394FB092D2ECCD56123C74F36E4C1F926001CE".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
+        })
+    );
+
+    // top ups have to use the denom the campaign was created with
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign is funded with a different denom"
+        ))
+    );
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[coin(1000, "uatom"), coin(1000, "uosmo")]),
+        "test_campaign_2".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Only one coin is allowed")));
+}
+
+#[test]
+fn test_deposit_schedule() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        Some(env.block.time),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("End time must be in the future"))
+    );
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        Some(env.block.time.plus_seconds(200)),
+        Some(env.block.time.plus_seconds(100)),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Start time must be before end time"))
+    );
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        Some(env.block.time.plus_seconds(100)),
+        Some(env.block.time.plus_seconds(200)),
+        None,
+    )
+    .unwrap();
+
+    let campaign = CAMPAIGN_POOL
+        .load(deps.as_ref().storage, "test_campaign_1".to_string())
+        .unwrap();
+    assert_eq!(campaign.start_time, Some(env.block.time.plus_seconds(100)));
+    assert_eq!(campaign.end_time, Some(env.block.time.plus_seconds(200)));
+
+    // top ups cannot change the schedule
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        Some(env.block.time.plus_seconds(300)),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign schedule can only be set when the campaign is created"
+        ))
+    );
+}
@@ -0,0 +1,199 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, Binary, StdError, Uint128};
+
+use crate::contract::{add_signer, claim, claim_batch, deposit, instantiate};
+use crate::msg::{ClaimItem, InstantiateMsg, SignedData};
+use crate::state::{KeyType, SignerKey};
+use crate::tests::{
+    ed25519_pubkey_of, sign, sign_ed25519, signer_pubkey, ED25519_SECRET, OTHER_SECRET,
+};
+
+fn signed_data(campaign_id: &str, nonce: u64) -> SignedData {
+    SignedData {
+        campaign_id: campaign_id.to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+fn item(campaign_id: &str, nonce: u64, signature: Binary) -> ClaimItem {
+    ClaimItem {
+        campaign_id: campaign_id.to_string(),
+        amount: Uint128::new(100),
+        denom: "".to_string(),
+        nonce,
+        signature,
+        cosignatures: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_ed25519_cosigner() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        signer_pubkey(),
+        KeyType::Ed25519,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid public key")));
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        ed25519_pubkey_of(&ED25519_SECRET),
+        KeyType::Ed25519,
+    )
+    .unwrap();
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_ed25519(&ED25519_SECRET, &signed_data("test_campaign_1", 1)),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign_ed25519(&OTHER_SECRET, &signed_data("test_campaign_1", 2)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+}
+
+#[test]
+fn test_ed25519_claim_batch() {
+    let mut deps = mock_dependencies_with_balance(&coins(3000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    for campaign_id in ["hsm_campaign_1", "hsm_campaign_2"] {
+        deposit(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &coins(1000, "")),
+            campaign_id.to_string(),
+            None,
+            None,
+            Some(SignerKey {
+                key_type: KeyType::Ed25519,
+                key: ed25519_pubkey_of(&ED25519_SECRET),
+            }),
+        )
+        .unwrap();
+    }
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "global_campaign".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // the ed25519 claims are verified together, the other one on its own
+    claim_batch(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        vec![
+            item(
+                "hsm_campaign_1",
+                1,
+                sign_ed25519(&ED25519_SECRET, &signed_data("hsm_campaign_1", 1)),
+            ),
+            item(
+                "hsm_campaign_2",
+                2,
+                sign_ed25519(&ED25519_SECRET, &signed_data("hsm_campaign_2", 2)),
+            ),
+            item(
+                "global_campaign",
+                3,
+                sign(&signed_data("global_campaign", 3)),
+            ),
+        ],
+    )
+    .unwrap();
+
+    // a bad signature fails the batch check and the claims are checked one by one
+    let res = claim_batch(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        vec![
+            item(
+                "hsm_campaign_1",
+                4,
+                sign_ed25519(&ED25519_SECRET, &signed_data("hsm_campaign_1", 4)),
+            ),
+            item(
+                "hsm_campaign_2",
+                5,
+                sign_ed25519(&OTHER_SECRET, &signed_data("hsm_campaign_2", 5)),
+            ),
+        ],
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+}
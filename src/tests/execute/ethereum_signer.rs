@@ -0,0 +1,192 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, Binary, StdError, Uint128};
+
+use crate::contract::{
+    add_signer, claim, deposit, instantiate, set_approval_threshold, set_required_signatures,
+};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::{KeyType, SignerKey};
+use crate::tests::{
+    eth_address_of, sign, sign_eip191, sign_with, signer_pubkey, ETH_SECRET, OTHER_SECRET,
+};
+
+fn signed_data(campaign_id: &str, nonce: u64, amount: u128) -> SignedData {
+    SignedData {
+        campaign_id: campaign_id.to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(amount),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_ethereum_cosigner() {
+    let mut deps = mock_dependencies_with_balance(&coins(10000, ""));
+    let env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(10000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        Binary(vec![1u8; 33]),
+        KeyType::Ethereum,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid Ethereum address")));
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        eth_address_of(&ETH_SECRET),
+        KeyType::Ethereum,
+    )
+    .unwrap();
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_eip191(&ETH_SECRET, &signed_data("test_campaign_1", 1, 100)),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // the address does not accept ADR-036 signatures of the same key
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign_with(&ETH_SECRET, &signed_data("test_campaign_1", 2, 100)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // both schemes count towards the required signatures
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2).unwrap();
+    set_approval_threshold(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        "".to_string(),
+        Some(Uint128::new(500)),
+    )
+    .unwrap();
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(1000),
+        3,
+        None,
+        sign(&signed_data("test_campaign_1", 3, 1000)),
+        Some(vec![sign_eip191(
+            &ETH_SECRET,
+            &signed_data("test_campaign_1", 3, 1000),
+        )]),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_ethereum_campaign_signer() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &coins(1000, "")),
+        "own_campaign".to_string(),
+        None,
+        None,
+        Some(SignerKey {
+            key_type: KeyType::Ethereum,
+            key: eth_address_of(&ETH_SECRET),
+        }),
+    )
+    .unwrap();
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "own_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_eip191(&OTHER_SECRET, &signed_data("own_campaign", 1, 100)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "own_campaign".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign_eip191(&ETH_SECRET, &signed_data("own_campaign", 2, 100)),
+        None,
+        None,
+    )
+    .unwrap();
+}
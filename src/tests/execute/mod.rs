@@ -1,5 +1,25 @@
+mod admin;
+mod campaign_signer;
 mod cancel;
 mod claim;
+mod claim_batch;
+mod claim_cumulative;
+mod claim_for;
+mod claim_merkle;
+mod denoms;
 mod deposit;
+mod ed25519_signer;
+mod ethereum_signer;
+mod prune_nonces;
+mod receive;
+mod revoke_nonces;
+mod roles;
+mod rotate_signer;
 mod set_cpool;
+mod set_hrp;
+mod signers;
+mod signing_domain;
+mod solvency_check;
+mod sweep_expired;
+mod voucher_expiry;
 mod withdraw;
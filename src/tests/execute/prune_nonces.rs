@@ -0,0 +1,169 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, Order, StdError, Uint128};
+
+use crate::contract::{cancel, claim, deposit, instantiate, prune_nonces, revoke_nonces};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::{NONCES, REVOKED_NONCES};
+use crate::tests::{sign, signer_pubkey};
+
+#[test]
+fn test_prune_nonces() {
+    let mut deps = mock_dependencies_with_balance(&coins(2000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // the same nonce is used by two claimers, 64 nonces share a storage entry
+    for (sender, nonce) in [("user1", 1), ("user2", 1), ("user2", 2), ("user2", 64)] {
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(sender, &[]),
+            "test_campaign_1".to_string(),
+            "".to_string(),
+            Uint128::new(100),
+            nonce,
+            None,
+            sign(&SignedData {
+                campaign_id: "test_campaign_1".to_string(),
+                nonce,
+                denom: "".to_string(),
+                amount: Uint128::new(100),
+                sender: Addr::unchecked(sender),
+                recipient: None,
+                relayer_fee: None,
+                expires_at: None,
+            }),
+            None,
+            None,
+        )
+        .unwrap();
+    }
+    revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        "user3".to_string(),
+        vec![1],
+    )
+    .unwrap();
+
+    let res = prune_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Some("test_campaign_1".to_string()),
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Campaign is still open")));
+
+    // ids that were never funded cannot be closed
+    let res = prune_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Some("test_campaign_2".to_string()),
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Campaign does not exist")));
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_2".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    cancel(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_1".to_string(),
+    )
+    .unwrap();
+
+    let res = prune_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        Some("test_campaign_1".to_string()),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can prune nonces"
+        ))
+    );
+
+    let res = prune_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Some("test_campaign_1".to_string()),
+        Some(2),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[2].value, "2");
+
+    let res = prune_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Some("test_campaign_1".to_string()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[2].value, "2");
+
+    let storage = deps.as_ref().storage;
+    assert!(NONCES
+        .sub_prefix("test_campaign_1")
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    assert!(REVOKED_NONCES
+        .sub_prefix("test_campaign_1")
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    // the pruned vouchers cannot be replayed against a new campaign with the same id
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Campaign id has been closed"))
+    );
+}
@@ -0,0 +1,268 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockQuerier};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, ContractResult, CosmosMsg, StdError, SubMsg,
+    SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+
+use crate::contract::{add_denom, cancel, claim, deposit, instantiate, receive, withdraw};
+use crate::msg::{InstantiateMsg, ReceiveMsg, SignedData};
+use crate::state::{Campaign, CAMPAIGN_POOL};
+use crate::tests::{sign, signer_pubkey};
+
+fn cw20_deposit(sender: &str, amount: u128, campaign_id: &str) -> Cw20ReceiveMsg {
+    Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_json_binary(&ReceiveMsg::Deposit {
+            campaign_id: campaign_id.to_string(),
+            start_time: None,
+            end_time: None,
+            signer: None,
+            signer_type: None,
+        })
+        .unwrap(),
+    }
+}
+
+fn cw20_transfer(token: &str, recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    }))
+}
+
+/// Makes the "token" contract report `balance` for the contract
+fn mock_token_balance(querier: &mut MockQuerier, balance: u128) {
+    querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "token" => {
+            match from_json(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(balance),
+                    })
+                    .unwrap(),
+                )),
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+}
+
+#[test]
+fn test_receive_deposit() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    mock_token_balance(&mut deps.querier, 1500);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "token".to_string(),
+        None,
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "other_token".to_string(),
+        None,
+    )
+    .unwrap();
+
+    receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("token", &[]),
+        cw20_deposit("sender", 1000, "test_campaign_1"),
+    )
+    .unwrap();
+
+    // top up from another holder of the same token
+    receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("token", &[]),
+        cw20_deposit("sender2", 500, "test_campaign_1"),
+    )
+    .unwrap();
+
+    let campaign = CAMPAIGN_POOL.load(deps.as_ref().storage, "test_campaign_1".to_string());
+    assert_eq!(
+        campaign,
+        Ok(Campaign {
+            amount: Uint128::new(1500),
+            owner: Addr::unchecked("sender"),
+            denom: Denom::Cw20(Addr::unchecked("token")),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
+        })
+    );
+
+    // a different token or native funds cannot be mixed into the campaign
+    let res = receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other_token", &[]),
+        cw20_deposit("sender", 500, "test_campaign_1"),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign is funded with a different denom"
+        ))
+    );
+
+    let res = deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(500, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign is funded with a different denom"
+        ))
+    );
+}
+
+#[test]
+fn test_receive_claim_and_cancel() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    mock_token_balance(&mut deps.querier, 1000);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "token".to_string(),
+        None,
+    )
+    .unwrap();
+
+    receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("token", &[]),
+        cw20_deposit("sender", 1000, "test_campaign_1"),
+    )
+    .unwrap();
+
+    // CW20 campaigns are claimed with the token address as denom
+    let resp = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "token".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 1,
+            denom: "token".to_string(),
+            amount: Uint128::new(400),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(resp.messages, vec![cw20_transfer("token", "user1", 400)]);
+
+    let resp = cancel(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_1".to_string(),
+    )
+    .unwrap();
+    assert_eq!(resp.messages, vec![cw20_transfer("token", "sender", 600)]);
+}
+
+#[test]
+fn test_withdraw_token() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    mock_token_balance(&mut deps.querier, 1000);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let resp = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(999),
+        None,
+        Some("token".to_string()),
+    )
+    .unwrap();
+    assert_eq!(resp.messages, vec![cw20_transfer("token", "creator", 999)]);
+
+    let res = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1001),
+        None,
+        Some("token".to_string()),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Not enough unallocated funds in the contract"
+        ))
+    );
+}
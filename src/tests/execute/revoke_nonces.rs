@@ -0,0 +1,121 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, DepsMut, Response, StdError, Uint128};
+
+use crate::contract::{claim, deposit, instantiate, revoke_nonces};
+use crate::crypto::pubkey_to_account;
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::tests::{sign, signer_pubkey};
+
+fn claim_nonce(deps: DepsMut, nonce: u64) -> Result<Response, StdError> {
+    let signature = sign(&SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    });
+    claim(
+        deps,
+        mock_env(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        nonce,
+        None,
+        signature,
+        None,
+        None,
+    )
+}
+
+#[test]
+fn test_revoke_nonces() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    claim_nonce(deps.as_mut(), 1).unwrap();
+
+    let res = revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![2],
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin, a signer manager or a signer can revoke nonces"
+        ))
+    );
+
+    let res = revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![],
+    );
+    assert_eq!(res, Err(StdError::generic_err("No nonces to revoke")));
+
+    // the claimed nonce is left as it is
+    let res = revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![1, 2],
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1].value, "1");
+
+    // the signer account can revoke its own vouchers
+    let signer = pubkey_to_account(&signer_pubkey(), "archway");
+    revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&signer, &[]),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![3],
+    )
+    .unwrap();
+
+    let res = claim_nonce(deps.as_mut(), 1);
+    assert_eq!(res, Err(StdError::generic_err("Nonce has been used")));
+    let res = claim_nonce(deps.as_mut(), 2);
+    assert_eq!(res, Err(StdError::generic_err("Nonce has been revoked")));
+    let res = claim_nonce(deps.as_mut(), 3);
+    assert_eq!(res, Err(StdError::generic_err("Nonce has been revoked")));
+
+    claim_nonce(deps.as_mut(), 4).unwrap();
+}
@@ -0,0 +1,212 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, BankMsg, CosmosMsg, StdError, SubMsg, Uint128};
+
+use crate::contract::{
+    add_signer, cancel, emergency_withdraw, grant_role, instantiate, revoke_role, set_cpool,
+    withdraw,
+};
+use crate::msg::InstantiateMsg;
+use crate::state::{KeyType, Role};
+use crate::tests::{pubkey_of, signer_pubkey, OTHER_SECRET};
+
+#[test]
+fn test_roles() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = grant_role(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("treasurer", &[]),
+        "treasurer".to_string(),
+        Role::Treasurer,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can manage roles"
+        ))
+    );
+
+    for (address, role) in [
+        ("treasurer", Role::Treasurer),
+        ("pool_manager", Role::PoolManager),
+        ("signer_manager", Role::SignerManager),
+    ] {
+        grant_role(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            address.to_string(),
+            role,
+        )
+        .unwrap();
+    }
+
+    let res = grant_role(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "treasurer".to_string(),
+        Role::Treasurer,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Address already has this role"))
+    );
+
+    // withdrawals by the treasurer are paid to the admin
+    let resp = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("treasurer", &[]),
+        Uint128::new(100),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "creator".to_string(),
+            amount: coins(100, ""),
+        }))]
+    );
+
+    // emergency withdrawals are kept to the admin
+    let res = emergency_withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("treasurer", &[]),
+        Uint128::new(100),
+        None,
+        None,
+        "compromised signer".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only contract owner can make emergency withdrawals"
+        ))
+    );
+
+    let res = set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("treasurer", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(100),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a pool manager can set the campaign pool"
+        ))
+    );
+    set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pool_manager", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+
+    // campaigns created by a pool manager are owned by the admin, so the refund goes there
+    let res = cancel(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pool_manager", &[]),
+        "test_campaign_1".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only campaign owner can cancel the campaign"
+        ))
+    );
+    let res = cancel(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "creator".to_string(),
+            amount: coins(100, ""),
+        }))]
+    );
+
+    let res = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pool_manager", &[]),
+        Uint128::new(100),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a treasurer can withdraw"
+        ))
+    );
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("signer_manager", &[]),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+
+    let res = revoke_role(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "treasurer".to_string(),
+        Role::PoolManager,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Address does not have this role"))
+    );
+
+    revoke_role(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "treasurer".to_string(),
+        Role::Treasurer,
+    )
+    .unwrap();
+    let res = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("treasurer", &[]),
+        Uint128::new(100),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a treasurer can withdraw"
+        ))
+    );
+}
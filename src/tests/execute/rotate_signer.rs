@@ -0,0 +1,210 @@
+use cosmwasm_std::testing::{
+    mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{coins, Addr, Binary, OwnedDeps, StdError, Uint128};
+
+use crate::contract::{add_signer, claim, deposit, instantiate, rotate_signer};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::KeyType;
+use crate::tests::{pubkey_of, sign_with, signer_pubkey, OTHER_SECRET, SIGNER_SECRET};
+
+fn signed_data(nonce: u64) -> SignedData {
+    SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn test_rotate_signer_with_grace_period() {
+    let mut deps = setup();
+    let env = mock_env();
+
+    rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        pubkey_of(&OTHER_SECRET),
+        Some(3600),
+    )
+    .unwrap();
+
+    // both keys are accepted during the grace period
+    for (nonce, secret) in [(1, &SIGNER_SECRET), (2, &OTHER_SECRET)] {
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user1", &[]),
+            "test_campaign_1".to_string(),
+            "".to_string(),
+            Uint128::new(100),
+            nonce,
+            None,
+            sign_with(secret, &signed_data(nonce)),
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    let mut later = env.clone();
+    later.block.time = env.block.time.plus_seconds(3600);
+
+    let res = claim(
+        deps.as_mut(),
+        later.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data(3)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    claim(
+        deps.as_mut(),
+        later,
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        4,
+        None,
+        sign_with(&OTHER_SECRET, &signed_data(4)),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_rotate_signer_immediately() {
+    let mut deps = setup();
+    let env = mock_env();
+
+    rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        pubkey_of(&OTHER_SECRET),
+        None,
+    )
+    .unwrap();
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data(1)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+}
+
+#[test]
+fn test_rotate_signer_invalid() {
+    let mut deps = setup();
+    let env = mock_env();
+
+    let res = rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        pubkey_of(&OTHER_SECRET),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings"
+        ))
+    );
+
+    let res = rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Binary(vec![2u8; 20]),
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid public key")));
+
+    let res = rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        signer_pubkey(),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Key is already the active signer"))
+    );
+
+    // the backend key and a co-signer have to stay distinct signers
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+    let res = rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        pubkey_of(&OTHER_SECRET),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Key is already registered as a co-signer"
+        ))
+    );
+}
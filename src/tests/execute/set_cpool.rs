@@ -1,20 +1,16 @@
-use std::vec;
-
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
-use cosmwasm_std::{
-    coins, from_binary, from_json, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg,
-    DepsMut, Env, MessageInfo, StdError, SubMsg, Uint128,
-};
+use cosmwasm_std::{coins, to_json_binary, Addr, Event, StdError, Uint128};
+use cw20::Denom;
 
-use crate::contract::{cancel, claim, deposit, instantiate, set_cpool, withdraw};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
-use crate::state::{Campaign, CAMPAIGN_POOL};
+use crate::contract::{deposit, instantiate, set_cpool};
+use crate::msg::InstantiateMsg;
+use crate::state::{Campaign, ALLOCATED, CAMPAIGN_POOL};
 
 #[test]
 fn test_set_new_cpool() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(100, ""));
     let env = mock_env();
 
     instantiate(
@@ -23,6 +19,7 @@ fn test_set_new_cpool() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -45,13 +42,19 @@ fn test_set_new_cpool() {
         Campaign {
             amount: Uint128::new(100),
             owner: Addr::unchecked("creator"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
         }
     );
 }
 
 #[test]
 fn test_set_existing_cpool() {
-    let mut deps = mock_dependencies();
+    // the deposit plus the funds sent along with set_cpool
+    let mut deps = mock_dependencies_with_balance(&coins(2000, ""));
     let env = mock_env();
 
     instantiate(
@@ -60,6 +63,7 @@ fn test_set_existing_cpool() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -69,6 +73,9 @@ fn test_set_existing_cpool() {
         env.clone(),
         mock_info("sender1", &coins(1000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -90,6 +97,11 @@ fn test_set_existing_cpool() {
         Campaign {
             amount: Uint128::new(2000),
             owner: Addr::unchecked("sender1"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
         }
     );
 }
@@ -105,6 +117,7 @@ fn test_set_new_cpool_unauthorized() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -118,6 +131,102 @@ fn test_set_new_cpool_unauthorized() {
     );
     assert_eq!(
         res.unwrap_err(),
-        StdError::generic_err("Only contract owner can set the campaign pool")
+        StdError::generic_err("Only the admin or a pool manager can set the campaign pool")
+    );
+}
+
+#[test]
+fn test_set_cpool_backed_only() {
+    let mut deps = mock_dependencies_with_balance(&coins(1500, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender1", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // only the 500 that are not allocated can back an increase
+    let res = set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(1501),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Not enough unallocated funds to back the campaign pool"
+        ))
+    );
+
+    let res = set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(1500),
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("set_cpool")
+            .add_attribute("campaign_id", "test_campaign_1")
+            .add_attribute("denom", "")
+            .add_attribute("old_amount", "1000")
+            .add_attribute("new_amount", "1500")]
+    );
+
+    let res = set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_2".to_string(),
+        Uint128::new(1),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Not enough unallocated funds to back the campaign pool"
+        ))
+    );
+
+    // decreasing a pool returns its funds to the reserve
+    set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        Uint128::new(700),
+    )
+    .unwrap();
+    set_cpool(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_2".to_string(),
+        Uint128::new(800),
+    )
+    .unwrap();
+    assert_eq!(
+        ALLOCATED.load(deps.as_ref().storage, "").unwrap(),
+        Uint128::new(1500)
     );
 }
@@ -0,0 +1,107 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, StdError, Uint128};
+
+use crate::contract::{claim, deposit, instantiate, set_hrp};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::HRP;
+use crate::tests::{sign, signer_pubkey};
+
+#[test]
+fn test_set_hrp() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("osmo".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let signed_data = |nonce: u64| SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    };
+
+    // the signer address in the document does not match the configured prefix
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&signed_data(1)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    let res = set_hrp(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "archway".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings"
+        ))
+    );
+
+    let res = set_hrp(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "".to_string(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid address prefix")));
+
+    set_hrp(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "archway".to_string(),
+    )
+    .unwrap();
+    assert_eq!(HRP.load(&deps.storage).unwrap(), "archway");
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign(&signed_data(2)),
+        None,
+        None,
+    )
+    .unwrap();
+}
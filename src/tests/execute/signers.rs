@@ -0,0 +1,316 @@
+use cosmwasm_std::testing::{
+    mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{coins, Addr, OwnedDeps, StdError, Uint128};
+
+use crate::contract::{
+    add_signer, claim, deposit, instantiate, remove_signer, rotate_signer, set_approval_threshold,
+    set_required_signatures,
+};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::KeyType;
+use crate::tests::{pubkey_of, sign_with, signer_pubkey, ETH_SECRET, OTHER_SECRET, SIGNER_SECRET};
+
+fn signed_data(nonce: u64, amount: u128) -> SignedData {
+    SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(amount),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies_with_balance(&coins(10000, ""));
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &coins(10000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn test_claim_above_approval_threshold() {
+    let mut deps = setup();
+    let env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2).unwrap();
+    set_approval_threshold(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        "".to_string(),
+        Some(Uint128::new(500)),
+    )
+    .unwrap();
+
+    // small claims need a single signer, including a co-signer
+    for (nonce, secret) in [(1, &SIGNER_SECRET), (2, &OTHER_SECRET)] {
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user1", &[]),
+            "test_campaign_1".to_string(),
+            "".to_string(),
+            Uint128::new(500),
+            nonce,
+            None,
+            sign_with(secret, &signed_data(nonce, 500)),
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(501),
+        3,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data(3, 501)),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Claim requires signatures from 2 distinct signers"
+        ))
+    );
+
+    // the same signer cannot approve twice
+    let signature = sign_with(&SIGNER_SECRET, &signed_data(4, 501));
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(501),
+        4,
+        None,
+        signature.clone(),
+        Some(vec![signature]),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Claim requires signatures from 2 distinct signers"
+        ))
+    );
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(501),
+        5,
+        None,
+        sign_with(&SIGNER_SECRET, &signed_data(5, 501)),
+        Some(vec![sign_with(&OTHER_SECRET, &signed_data(5, 501))]),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_previous_key_is_not_a_second_signer() {
+    let mut deps = setup();
+    let mut env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+        Some(3600),
+    )
+    .unwrap();
+
+    // during the grace period the old key already signs for the backend
+    let res = add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        signer_pubkey(),
+        KeyType::Secp256k1,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Signer is still accepted as the previous backend key"
+        ))
+    );
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&ETH_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2).unwrap();
+    set_approval_threshold(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        "".to_string(),
+        Some(Uint128::new(500)),
+    )
+    .unwrap();
+
+    // the old key alone cannot approve a large claim
+    let signature = sign_with(&SIGNER_SECRET, &signed_data(1, 501));
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(501),
+        1,
+        None,
+        signature.clone(),
+        Some(vec![signature]),
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Claim requires signatures from 2 distinct signers"
+        ))
+    );
+
+    // once the grace period is over it can be registered as a co-signer
+    env.block.time = env.block.time.plus_seconds(3600);
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        signer_pubkey(),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_manage_signers() {
+    let mut deps = setup();
+    let env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    let res = add_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings"
+        ))
+    );
+
+    let res = add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        signer_pubkey(),
+        KeyType::Secp256k1,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Signer is already registered"))
+    );
+
+    let res = set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Required signatures must be between 1 and the number of signers"
+        ))
+    );
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2).unwrap();
+
+    let res = remove_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Cannot remove a signer needed for the required signatures"
+        ))
+    );
+
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 1).unwrap();
+    remove_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+    )
+    .unwrap();
+
+    let res = remove_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Signer is not registered")));
+}
@@ -0,0 +1,185 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, StdError, Uint128};
+
+use crate::contract::{claim, deposit, instantiate, set_legacy_signatures};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::tests::{sign, sign_legacy, signer_pubkey};
+
+fn signed_data(nonce: u64) -> SignedData {
+    SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_signature_bound_to_deployment() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // signed for another chain
+    let mut other_chain = env.clone();
+    other_chain.block.chain_id = "cosmos-mainnet-1".to_string();
+    let res = claim(
+        deps.as_mut(),
+        other_chain,
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&signed_data(1)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    // signed for another pool instance
+    let mut other_contract = env.clone();
+    other_contract.contract.address = Addr::unchecked("cosmos2other");
+    let res = claim(
+        deps.as_mut(),
+        other_contract,
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign(&signed_data(2)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        sign(&signed_data(3)),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_legacy_signatures() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // rejected on new deployments
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign_legacy(&signed_data(1)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    let res = set_legacy_signatures(deps.as_mut(), env.clone(), mock_info("user1", &[]), true);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a signer manager can change signature settings"
+        ))
+    );
+
+    set_legacy_signatures(deps.as_mut(), env.clone(), mock_info("creator", &[]), true).unwrap();
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign_legacy(&signed_data(2)),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // phased out again
+    set_legacy_signatures(deps.as_mut(), env.clone(), mock_info("creator", &[]), false).unwrap();
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        sign_legacy(&signed_data(3)),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+}
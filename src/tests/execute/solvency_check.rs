@@ -0,0 +1,226 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, StdError, Uint128};
+
+use crate::contract::{add_denom, execute, grant_role, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg, SignedData};
+use crate::state::{Role, ALLOCATED, CAMPAIGN_POOL};
+use crate::tests::{sign, signer_pubkey};
+
+fn add_denom_msg(denom: &str) -> ExecuteMsg {
+    ExecuteMsg::AddDenom {
+        denom: denom.to_string(),
+        min_deposit: None,
+    }
+}
+
+#[test]
+fn test_solvency_check() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "ucosm"));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "ucosm".to_string(),
+        None,
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "ucosm")),
+        ExecuteMsg::Deposit {
+            campaign_id: "test_campaign_1".to_string(),
+            start_time: None,
+            end_time: None,
+            signer: None,
+            signer_type: None,
+        },
+    )
+    .unwrap();
+
+    // payouts of the response are taken into account, the mock balance does not change
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Claim {
+            campaign_id: "test_campaign_1".to_string(),
+            amount: Uint128::new(100),
+            denom: "ucosm".to_string(),
+            nonce: 1,
+            recipient: None,
+            signature: sign(&SignedData {
+                campaign_id: "test_campaign_1".to_string(),
+                nonce: 1,
+                denom: "ucosm".to_string(),
+                amount: Uint128::new(100),
+                sender: Addr::unchecked("user1"),
+                recipient: None,
+                relayer_fee: None,
+                expires_at: None,
+            }),
+            cosignatures: None,
+            expires_at: None,
+        },
+    )
+    .unwrap();
+
+    // only the denoms a transaction moves are checked
+    ALLOCATED
+        .save(deps.as_mut().storage, "uatom", &Uint128::new(500))
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        add_denom_msg("uatom"),
+    )
+    .unwrap();
+
+    // emergency withdrawals may leave campaigns unbacked
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::EmergencyWithdraw {
+            amount: Uint128::new(1000),
+            denom: Some("ucosm".to_string()),
+            token: None,
+            reason: "compromised signer".to_string(),
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![]);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Claim {
+            campaign_id: "test_campaign_1".to_string(),
+            amount: Uint128::new(100),
+            denom: "ucosm".to_string(),
+            nonce: 2,
+            recipient: None,
+            signature: sign(&SignedData {
+                campaign_id: "test_campaign_1".to_string(),
+                nonce: 2,
+                denom: "ucosm".to_string(),
+                amount: Uint128::new(100),
+                sender: Addr::unchecked("user1"),
+                recipient: None,
+                relayer_fee: None,
+                expires_at: None,
+            }),
+            cosignatures: None,
+            expires_at: None,
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Contract is insolvent in ucosm: 800 allocated, 0 available"
+        ))
+    );
+
+    // other denoms keep working
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        add_denom_msg("uosmo"),
+    )
+    .unwrap();
+
+    // the pool manager cannot leave the pool unbacked, the admin can shrink it to recover
+    grant_role(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "pool_manager".to_string(),
+        Role::PoolManager,
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pool_manager", &[]),
+        ExecuteMsg::SetCpool {
+            campaign_id: "test_campaign_1".to_string(),
+            amount: Uint128::new(700),
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Contract is insolvent in ucosm: 700 allocated, 0 available"
+        ))
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::SetCpool {
+            campaign_id: "test_campaign_1".to_string(),
+            amount: Uint128::new(600),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        ALLOCATED.load(deps.as_ref().storage, "ucosm").unwrap(),
+        Uint128::new(600)
+    );
+
+    // campaign pools have to add up to the allocated total
+    let mut campaign = CAMPAIGN_POOL
+        .load(&deps.storage, "test_campaign_1".to_string())
+        .unwrap();
+    campaign.amount += Uint128::new(100);
+    CAMPAIGN_POOL
+        .save(&mut deps.storage, "test_campaign_1".to_string(), &campaign)
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Claim {
+            campaign_id: "test_campaign_1".to_string(),
+            amount: Uint128::new(100),
+            denom: "ucosm".to_string(),
+            nonce: 3,
+            recipient: None,
+            signature: sign(&SignedData {
+                campaign_id: "test_campaign_1".to_string(),
+                nonce: 3,
+                denom: "ucosm".to_string(),
+                amount: Uint128::new(100),
+                sender: Addr::unchecked("user1"),
+                recipient: None,
+                relayer_fee: None,
+                expires_at: None,
+            }),
+            cosignatures: None,
+            expires_at: None,
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Campaign pools in ucosm add up to 600 but 500 is allocated"
+        ))
+    );
+}
@@ -0,0 +1,158 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, to_json_binary, BankMsg, CosmosMsg, Event, SubMsg};
+
+use crate::contract::{deposit, instantiate, sweep_expired};
+use crate::msg::InstantiateMsg;
+use crate::state::CAMPAIGN_POOL;
+
+#[test]
+fn test_sweep_expired() {
+    let mut deps = mock_dependencies_with_balance(&coins(300, ""));
+    let mut env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender1", &coins(100, "")),
+        "test_campaign_1".to_string(),
+        None,
+        Some(env.block.time.plus_seconds(100)),
+        None,
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender2", &coins(200, "")),
+        "test_campaign_2".to_string(),
+        None,
+        Some(env.block.time.plus_seconds(200)),
+        None,
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(150);
+
+    let res = sweep_expired(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        vec!["test_campaign_1".to_string(), "test_campaign_2".to_string()],
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "sender1".to_string(),
+            amount: coins(100, ""),
+        }))]
+    );
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("sweep_expired")
+                .add_attribute("campaign_id", "test_campaign_1")
+                .add_attribute("owner", "sender1")
+                .add_attribute("amount", "100"),
+            Event::new("sweep_skipped")
+                .add_attribute("campaign_id", "test_campaign_2")
+                .add_attribute("reason", "not_ended"),
+        ]
+    );
+    assert!(CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_2".to_string()));
+
+    env.block.time = env.block.time.plus_seconds(50);
+
+    let resp = sweep_expired(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        vec!["test_campaign_1".to_string(), "test_campaign_2".to_string()],
+    )
+    .unwrap();
+
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "sender2".to_string(),
+            amount: coins(200, ""),
+        }))]
+    );
+    assert_eq!(
+        resp.events,
+        vec![
+            Event::new("sweep_skipped")
+                .add_attribute("campaign_id", "test_campaign_1")
+                .add_attribute("reason", "not_found"),
+            Event::new("sweep_expired")
+                .add_attribute("campaign_id", "test_campaign_2")
+                .add_attribute("owner", "sender2")
+                .add_attribute("amount", "200"),
+        ]
+    );
+
+    assert!(!CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
+    assert!(!CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_2".to_string()));
+}
+
+#[test]
+fn test_sweep_campaign_without_end_time() {
+    let mut deps = mock_dependencies_with_balance(&coins(100, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender1", &coins(100, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // skipped campaigns do not fail the batch
+    let res = sweep_expired(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        vec!["test_campaign_1".to_string(), "test_campaign_2".to_string()],
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("sweep_skipped")
+                .add_attribute("campaign_id", "test_campaign_1")
+                .add_attribute("reason", "not_ended"),
+            Event::new("sweep_skipped")
+                .add_attribute("campaign_id", "test_campaign_2")
+                .add_attribute("reason", "not_found"),
+        ]
+    );
+    assert!(CAMPAIGN_POOL.has(deps.as_ref().storage, "test_campaign_1".to_string()));
+}
@@ -0,0 +1,193 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, StdError, Uint128};
+use cw_utils::Expiration;
+
+use crate::contract::{claim, claim_cumulative, deposit, instantiate};
+use crate::msg::{InstantiateMsg, SignedCumulativeData, SignedData};
+use crate::tests::{sign, signer_pubkey};
+
+fn signed_data(nonce: u64, expires_at: Option<Expiration>) -> SignedData {
+    SignedData {
+        campaign_id: "test_campaign_1".to_string(),
+        nonce,
+        denom: "".to_string(),
+        amount: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        recipient: None,
+        relayer_fee: None,
+        expires_at,
+    }
+}
+
+#[test]
+fn test_voucher_expiry() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let by_height = Expiration::AtHeight(env.block.height + 1);
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&signed_data(1, Some(by_height))),
+        None,
+        Some(by_height),
+    )
+    .unwrap();
+
+    // the expiry is part of the signed payload
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        2,
+        None,
+        sign(&signed_data(2, Some(by_height))),
+        None,
+        Some(Expiration::AtHeight(env.block.height + 100)),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        3,
+        None,
+        sign(&signed_data(3, Some(by_height))),
+        None,
+        None,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid signature")));
+
+    let mut later = env.clone();
+    later.block.height += 1;
+    let res = claim(
+        deps.as_mut(),
+        later,
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        4,
+        None,
+        sign(&signed_data(4, Some(by_height))),
+        None,
+        Some(by_height),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Voucher has expired")));
+
+    let by_time = Expiration::AtTime(env.block.time);
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        5,
+        None,
+        sign(&signed_data(5, Some(by_time))),
+        None,
+        Some(by_time),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Voucher has expired")));
+}
+
+#[test]
+fn test_cumulative_voucher_expiry() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let expires_at = Expiration::AtTime(env.block.time.plus_seconds(60));
+    let signature = sign(&SignedCumulativeData {
+        campaign_id: "test_campaign_1".to_string(),
+        denom: "".to_string(),
+        total: Uint128::new(100),
+        sender: Addr::unchecked("user1"),
+        expires_at: Some(expires_at),
+    });
+
+    let mut later = env.clone();
+    later.block.time = env.block.time.plus_seconds(60);
+    let res = claim_cumulative(
+        deps.as_mut(),
+        later,
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        signature.clone(),
+        None,
+        Some(expires_at),
+    );
+    assert_eq!(res, Err(StdError::generic_err("Voucher has expired")));
+
+    claim_cumulative(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        signature,
+        None,
+        Some(expires_at),
+    )
+    .unwrap();
+}
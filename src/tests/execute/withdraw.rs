@@ -1,16 +1,10 @@
-use std::vec;
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg, Event, StdError, SubMsg, Uint128};
 
-use cosmwasm_std::testing::{
-    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
-};
-use cosmwasm_std::{
-    coins, from_binary, from_json, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg,
-    DepsMut, Env, MessageInfo, StdError, SubMsg, Uint128,
-};
-
-use crate::contract::{cancel, claim, deposit, instantiate, set_cpool, withdraw};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
-use crate::state::{Campaign, CAMPAIGN_POOL};
+use crate::contract::{cancel, claim, deposit, emergency_withdraw, instantiate, withdraw};
+use crate::msg::{InstantiateMsg, SignedData};
+use crate::state::ALLOCATED;
+use crate::tests::{sign, signer_pubkey};
 
 #[test]
 fn test_withdraw() {
@@ -22,7 +16,8 @@ fn test_withdraw() {
         env.clone(),
         mock_info("creator", &[]),
         InstantiateMsg {
-            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -32,18 +27,9 @@ fn test_withdraw() {
         env.clone(),
         mock_info("sender", &coins(1000, "")),
         "test_campaign_1".to_string(),
-    )
-    .unwrap();
-
-    // try to claim from user1
-    claim(
-        deps.as_mut(),
-        env.clone(),
-        mock_info("user1", &coins(999, "")),
-        "test_campaign_1".to_string(),
-        "test_token".to_string(),
-        Uint128::new(999),
-        "test_nonce_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -53,6 +39,8 @@ fn test_withdraw() {
         env.clone(),
         mock_info("creator", &[]),
         Uint128::new(999),
+        None,
+        None,
     )
     .unwrap();
 
@@ -75,7 +63,8 @@ fn test_withdraw_unauthorized() {
         env.clone(),
         mock_info("creator", &[]),
         InstantiateMsg {
-            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -85,27 +74,214 @@ fn test_withdraw_unauthorized() {
         env.clone(),
         mock_info("sender", &coins(1000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
-    // try to claim from user1
+    // try to withdraw
+    let res = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_creator", &[]),
+        Uint128::new(999),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Only the admin or a treasurer can withdraw"
+        ))
+    );
+}
+
+#[test]
+fn test_withdraw_surplus_only() {
+    let mut deps = mock_dependencies_with_balance(&coins(1999, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    for campaign_id in ["test_campaign_1", "test_campaign_2"] {
+        deposit(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("sender", &coins(500, "")),
+            campaign_id.to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    let res = withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1000),
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Not enough unallocated funds in the contract"
+        ))
+    );
+
+    // claims and refunds release their funds, the bank balance is left as it is in the mock
     claim(
         deps.as_mut(),
         env.clone(),
-        mock_info("user1", &coins(999, "")),
+        mock_info("user1", &[]),
         "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 1,
+            denom: "".to_string(),
+            amount: Uint128::new(100),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
     )
     .unwrap();
+    cancel(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &[]),
+        "test_campaign_2".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        ALLOCATED.load(deps.as_ref().storage, "").unwrap(),
+        Uint128::new(400)
+    );
 
-    // try to withdraw
-    let res = withdraw(
+    withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1599),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_emergency_withdraw() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let res = emergency_withdraw(
         deps.as_mut(),
         env.clone(),
         mock_info("not_creator", &[]),
-        Uint128::new(999),
+        Uint128::new(1000),
+        None,
+        None,
+        "compromised signer".to_string(),
     );
     assert_eq!(
         res,
-        Err(StdError::generic_err("Only contract owner can withdraw"))
+        Err(StdError::generic_err(
+            "Only contract owner can make emergency withdrawals"
+        ))
+    );
+
+    let res = emergency_withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1000),
+        None,
+        None,
+        " ".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Emergency withdrawals need a reason"))
+    );
+
+    let res = emergency_withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1001),
+        None,
+        None,
+        "compromised signer".to_string(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Not enough funds in the contract"))
+    );
+
+    let resp = emergency_withdraw(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        Uint128::new(1000),
+        None,
+        None,
+        "compromised signer".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "creator".to_string(),
+            amount: coins(1000, ""),
+        }))]
+    );
+    assert_eq!(
+        resp.events,
+        vec![Event::new("emergency_withdraw")
+            .add_attribute("denom", "")
+            .add_attribute("amount", "1000")
+            .add_attribute("allocated", "1000")
+            .add_attribute("reason", "compromised signer")]
     );
 }
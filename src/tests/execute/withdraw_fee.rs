@@ -23,6 +23,7 @@ fn test_withdraw_fee() {
         mock_info("creator", &[]),
         InstantiateMsg {
             claim_reward_fee: Some(Uint128::new(999)),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -32,6 +33,9 @@ fn test_withdraw_fee() {
         env.clone(),
         mock_info("sender1", &coins(100, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -40,6 +44,9 @@ fn test_withdraw_fee() {
         env.clone(),
         mock_info("sender2", &coins(200, "")),
         "test_campaign_2".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -65,6 +72,7 @@ fn test_withdraw_fee_unauthorized() {
         mock_info("creator", &[]),
         InstantiateMsg {
             claim_reward_fee: Some(Uint128::new(999)),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -74,6 +82,9 @@ fn test_withdraw_fee_unauthorized() {
         env.clone(),
         mock_info("sender1", &coins(100, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -82,6 +93,9 @@ fn test_withdraw_fee_unauthorized() {
         env.clone(),
         mock_info("sender2", &coins(200, "")),
         "test_campaign_2".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
@@ -89,6 +103,6 @@ fn test_withdraw_fee_unauthorized() {
     let res = withdraw_fee(deps.as_mut(), env.clone(), mock_info("not_creator", &[]));
     assert_eq!(
         res,
-        Err(StdError::generic_err("Only contract owner can withdraw"))
+        Err(StdError::generic_err("Only the admin or a treasurer can withdraw"))
     );
 }
@@ -1,9 +1,10 @@
+use bech32::{ToBase32, Variant};
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{to_json_binary, Api};
+use cosmwasm_std::{to_json_binary, Addr, Api, StdError};
 
 use crate::contract::instantiate;
 use crate::msg::InstantiateMsg;
-use crate::state::{ADMIN, PUBKEY};
+use crate::state::{ADMIN, HRP, PUBKEY};
 
 #[test]
 fn test_instantiate_default() {
@@ -12,6 +13,7 @@ fn test_instantiate_default() {
     let env = mock_env();
     let msg = InstantiateMsg {
         pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+        hrp: Some("archway".to_string()),
     };
 
     instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
@@ -27,6 +29,7 @@ fn test_instantiate_with_custom_claim_fee() {
     let env = mock_env();
     let msg = InstantiateMsg {
         pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+        hrp: Some("archway".to_string()),
     };
 
     instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
@@ -34,4 +37,54 @@ fn test_instantiate_with_custom_claim_fee() {
     let admin = ADMIN.load(deps.as_ref().storage).unwrap();
     let pubkey = PUBKEY.load(&deps.storage).unwrap();
     assert_eq!(admin, deps.api.addr_canonicalize("sender").unwrap());
+    assert_eq!(pubkey, msg.pubkey);
+}
+
+#[test]
+fn test_instantiate_hrp() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("sender", &[]);
+    let mut env = mock_env();
+
+    // mock contract addresses are not bech32
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: None,
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Cannot derive the address prefix from the contract address, pass hrp explicitly"
+        ))
+    );
+
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("Neutron".to_string()),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("Invalid address prefix")));
+
+    env.contract.address =
+        Addr::unchecked(bech32::encode("neutron", [1u8; 32].to_base32(), Variant::Bech32).unwrap());
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(HRP.load(&deps.storage).unwrap(), "neutron");
 }
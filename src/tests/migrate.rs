@@ -0,0 +1,230 @@
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coins, from_json, Addr, Api, BankMsg, Coin, CosmosMsg, Empty, Env, Order, StdError,
+    Storage, SubMsg, Uint128,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::Denom;
+
+use crate::contract::{claim, migrate, prune_nonces};
+use crate::msg::{ExecuteMsg, SignedData};
+use crate::state::{
+    AllowedDenom, Campaign, ADMIN, ALLOCATED, ALLOWED_DENOMS, CAMPAIGN_POOL, HRP, LEGACY_NONCES,
+    LEGACY_SIGNATURES, PUBKEY,
+};
+
+use super::{sign_legacy, signer_pubkey};
+
+fn archway_env() -> Env {
+    let mut env = mock_env();
+    env.contract.address =
+        Addr::unchecked(bech32::encode("archway", [1u8; 32].to_base32(), Variant::Bech32).unwrap());
+    env
+}
+
+#[test]
+fn test_migrate_from_baseline() {
+    let mut deps = mock_dependencies();
+    let env = archway_env();
+
+    // store as left by the 0.1.2 release
+    set_contract_version(&mut deps.storage, "crates.io:reward_pool", "0.1.2").unwrap();
+    ADMIN
+        .save(
+            &mut deps.storage,
+            &deps.api.addr_canonicalize("creator").unwrap(),
+        )
+        .unwrap();
+    PUBKEY.save(&mut deps.storage, &signer_pubkey()).unwrap();
+
+    migrate(deps.as_mut(), env.clone(), Empty {}).unwrap();
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(HRP.load(&deps.storage).unwrap(), "archway");
+    assert!(LEGACY_SIGNATURES.load(&deps.storage).unwrap());
+    assert_eq!(
+        ALLOWED_DENOMS.load(&deps.storage, "").unwrap(),
+        AllowedDenom { min_deposit: None }
+    );
+}
+
+#[test]
+fn test_migrate_from_current_version() {
+    let mut deps = mock_dependencies();
+    let env = archway_env();
+
+    set_contract_version(
+        &mut deps.storage,
+        "crates.io:reward_pool",
+        env!("CARGO_PKG_VERSION"),
+    )
+    .unwrap();
+
+    let res = migrate(deps.as_mut(), env.clone(), Empty {});
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Cannot upgrade from a newer contract version"
+        ))
+    );
+}
+
+#[test]
+fn test_migrate_other_contract() {
+    let mut deps = mock_dependencies();
+    let env = archway_env();
+
+    set_contract_version(&mut deps.storage, "crates.io:other", "0.1.0").unwrap();
+
+    let res = migrate(deps.as_mut(), env.clone(), Empty {});
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "Can only upgrade from same contract type"
+        ))
+    );
+}
+
+#[test]
+fn test_migrate_baseline_campaign() {
+    let mut deps = mock_dependencies();
+    let env = archway_env();
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(1000, ""));
+
+    set_contract_version(&mut deps.storage, "crates.io:reward_pool", "0.1.2").unwrap();
+    ADMIN
+        .save(
+            &mut deps.storage,
+            &deps.api.addr_canonicalize("creator").unwrap(),
+        )
+        .unwrap();
+    PUBKEY.save(&mut deps.storage, &signer_pubkey()).unwrap();
+    // campaigns of the 0.1.2 release only had an amount and an owner
+    deps.storage.set(
+        &CAMPAIGN_POOL.key("test_campaign_1".to_string()),
+        br#"{"amount":"1000","owner":"sender"}"#,
+    );
+
+    migrate(deps.as_mut(), env.clone(), Empty {}).unwrap();
+
+    assert_eq!(
+        CAMPAIGN_POOL
+            .load(&deps.storage, "test_campaign_1".to_string())
+            .unwrap(),
+        Campaign {
+            amount: Uint128::new(1000),
+            owner: Addr::unchecked("sender"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
+        }
+    );
+    assert_eq!(
+        ALLOCATED.load(&deps.storage, "").unwrap(),
+        Uint128::new(1000)
+    );
+
+    // vouchers issued by the 0.1.2 backend are not bound to a signing domain
+    let res = claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(400),
+        1,
+        None,
+        sign_legacy(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 1,
+            denom: "".to_string(),
+            amount: Uint128::new(400),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: vec![Coin {
+                denom: "".to_string(),
+                amount: Uint128::new(400),
+            }],
+        }))]
+    );
+    assert_eq!(
+        CAMPAIGN_POOL
+            .load(&deps.storage, "test_campaign_1".to_string())
+            .unwrap()
+            .amount,
+        Uint128::new(600)
+    );
+}
+
+#[test]
+fn test_migrate_keeps_baseline_nonces() {
+    let mut deps = mock_dependencies();
+    let env = archway_env();
+
+    set_contract_version(&mut deps.storage, "crates.io:reward_pool", "0.1.2").unwrap();
+    ADMIN
+        .save(
+            &mut deps.storage,
+            &deps.api.addr_canonicalize("creator").unwrap(),
+        )
+        .unwrap();
+    PUBKEY.save(&mut deps.storage, &signer_pubkey()).unwrap();
+    for nonce in ["test_nonce_1", "test_nonce_2", "test_nonce_3"] {
+        deps.storage.set(&LEGACY_NONCES.key(nonce), b"true");
+    }
+
+    // the list can be arbitrarily long, migrating leaves it to be pruned in pages
+    migrate(deps.as_mut(), env.clone(), Empty {}).unwrap();
+    assert_eq!(
+        LEGACY_NONCES
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        3
+    );
+
+    for pruned in ["2", "1", "0"] {
+        let res = prune_nonces(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            None,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![attr("method", "prune_nonces"), attr("pruned", pruned)]
+        );
+    }
+    assert!(LEGACY_NONCES
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    // claims of the 0.1.2 release carried string nonces, they are no longer accepted
+    let res = from_json::<ExecuteMsg>(
+        br#"{"claim":{"campaign_id":"test_campaign_1","amount":"400","denom":"","nonce":"test_nonce_3","signature":""}}"#,
+    );
+    assert!(res.is_err());
+    let res = from_json::<ExecuteMsg>(
+        br#"{"claim":{"campaign_id":"test_campaign_1","amount":"400","denom":"","nonce":3,"signature":""}}"#,
+    );
+    assert!(res.is_ok());
+}
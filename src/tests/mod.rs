@@ -1,3 +1,118 @@
+mod crypto;
 mod execute;
 mod instantiate;
-mod query;
\ No newline at end of file
+mod migrate;
+mod query;
+
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Binary;
+use k256::ecdsa::signature::DigestSigner;
+use k256::ecdsa::{Signature, SigningKey};
+use k256::sha2::{Digest, Sha256};
+use serde::Serialize;
+use sha3::Keccak256;
+
+use crate::crypto::{
+    ethereum_address, generate_amino_transaction_string, pubkey_to_account, signed_document,
+    signing_domain,
+};
+use crate::msg::DomainSigned;
+
+// prefix the test contracts are instantiated with
+const HRP: &str = "archway";
+
+// fixed backend key used to produce claim signatures in tests
+pub const SIGNER_SECRET: [u8; 32] = [7u8; 32];
+// key the backend rotates to
+pub const OTHER_SECRET: [u8; 32] = [8u8; 32];
+// key of the EVM-facing backend
+pub const ETH_SECRET: [u8; 32] = [9u8; 32];
+// HSM-backed ed25519 key
+pub const ED25519_SECRET: [u8; 32] = [10u8; 32];
+
+fn signing_key(secret: &[u8; 32]) -> SigningKey {
+    SigningKey::from_bytes(secret.into()).unwrap()
+}
+
+pub fn pubkey_of(secret: &[u8; 32]) -> Binary {
+    Binary(
+        signing_key(secret)
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec(),
+    )
+}
+
+pub fn signer_pubkey() -> Binary {
+    pubkey_of(&SIGNER_SECRET)
+}
+
+/// Signs `data` for the contract in `mock_env()`
+pub fn sign<T: Serialize>(data: &T) -> Binary {
+    sign_with(&SIGNER_SECRET, data)
+}
+
+pub fn sign_with<T: Serialize>(secret: &[u8; 32], data: &T) -> Binary {
+    sign_document(
+        secret,
+        &DomainSigned {
+            domain: signing_domain(&mock_env()),
+            data,
+        },
+    )
+}
+
+/// Signs `data` without a signing domain, as done before it was introduced
+pub fn sign_legacy<T: Serialize>(data: &T) -> Binary {
+    sign_document(&SIGNER_SECRET, data)
+}
+
+fn sign_document<T: Serialize>(secret: &[u8; 32], data: &T) -> Binary {
+    let document = generate_amino_transaction_string(
+        &pubkey_to_account(&pubkey_of(secret), HRP),
+        &cosmwasm_std::to_json_string(data).unwrap(),
+    );
+    let signature: Signature = signing_key(secret).sign_digest(Sha256::new_with_prefix(document));
+    Binary(signature.to_bytes().to_vec())
+}
+
+pub fn eth_address_of(secret: &[u8; 32]) -> Binary {
+    let pubkey = signing_key(secret).verifying_key().to_encoded_point(false);
+    Binary(ethereum_address(pubkey.as_bytes()).to_vec())
+}
+
+/// Signs `data` for the contract in `mock_env()` like `personal_sign` does
+pub fn sign_eip191<T: Serialize>(secret: &[u8; 32], data: &T) -> Binary {
+    let message = cosmwasm_std::to_json_string(&DomainSigned {
+        domain: signing_domain(&mock_env()),
+        data,
+    })
+    .unwrap();
+    let digest =
+        Keccak256::new_with_prefix(format!("\x19Ethereum Signed Message:\n{}", message.len()))
+            .chain_update(message);
+    let (signature, recovery_id) = signing_key(secret).sign_digest_recoverable(digest).unwrap();
+
+    let mut signature = signature.to_bytes().to_vec();
+    signature.push(27 + recovery_id.to_byte());
+    Binary(signature)
+}
+
+pub fn ed25519_pubkey_of(secret: &[u8; 32]) -> Binary {
+    Binary(
+        ed25519_dalek::SigningKey::from_bytes(secret)
+            .verifying_key()
+            .to_bytes()
+            .to_vec(),
+    )
+}
+
+/// Signs the document of `data` for the contract in `mock_env()` with an ed25519 key
+pub fn sign_ed25519<T: Serialize>(secret: &[u8; 32], data: &T) -> Binary {
+    use ed25519_dalek::Signer;
+
+    let message = signed_document(&mock_env(), data).unwrap();
+    let signature = ed25519_dalek::SigningKey::from_bytes(secret).sign(message.as_bytes());
+    Binary(signature.to_bytes().to_vec())
+}
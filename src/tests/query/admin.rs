@@ -0,0 +1,55 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr};
+use cw_utils::Expiration;
+
+use crate::contract::{instantiate, propose_admin, query_admin};
+use crate::msg::{AdminResponse, InstantiateMsg};
+use crate::state::PendingAdmin;
+use crate::tests::signer_pubkey;
+
+#[test]
+fn test_query_admin() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = query_admin(deps.as_ref(), env.clone()).unwrap();
+    let admin: AdminResponse = from_json(res).unwrap();
+    assert_eq!(
+        admin,
+        AdminResponse {
+            admin: Addr::unchecked("creator"),
+            pending_admin: None,
+        }
+    );
+
+    let expires_at = Expiration::AtTime(env.block.time.plus_seconds(3600));
+    propose_admin(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "new_admin".to_string(),
+        Some(expires_at),
+    )
+    .unwrap();
+
+    let res = query_admin(deps.as_ref(), env.clone()).unwrap();
+    let admin: AdminResponse = from_json(res).unwrap();
+    assert_eq!(
+        admin.pending_admin,
+        Some(PendingAdmin {
+            address: Addr::unchecked("new_admin"),
+            expires_at: Some(expires_at),
+        })
+    );
+}
@@ -0,0 +1,68 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, Uint128};
+
+use crate::contract::{
+    add_signer, instantiate, query_approval_policy, set_approval_threshold, set_required_signatures,
+};
+use crate::msg::{ApprovalPolicyResponse, ApprovalThresholdResponse, InstantiateMsg};
+use crate::state::KeyType;
+use crate::tests::{pubkey_of, signer_pubkey, OTHER_SECRET};
+
+#[test]
+fn test_query_approval_policy() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let admin = mock_info("creator", &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = query_approval_policy(deps.as_ref(), env.clone()).unwrap();
+    let policy: ApprovalPolicyResponse = from_json(res).unwrap();
+    assert_eq!(
+        policy,
+        ApprovalPolicyResponse {
+            required_signatures: 1,
+            thresholds: vec![],
+        }
+    );
+
+    add_signer(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        pubkey_of(&OTHER_SECRET),
+        KeyType::Secp256k1,
+    )
+    .unwrap();
+    set_required_signatures(deps.as_mut(), env.clone(), admin.clone(), 2).unwrap();
+    set_approval_threshold(
+        deps.as_mut(),
+        env.clone(),
+        admin.clone(),
+        "uusdc".to_string(),
+        Some(Uint128::new(1000)),
+    )
+    .unwrap();
+
+    let res = query_approval_policy(deps.as_ref(), env.clone()).unwrap();
+    let policy: ApprovalPolicyResponse = from_json(res).unwrap();
+    assert_eq!(
+        policy,
+        ApprovalPolicyResponse {
+            required_signatures: 2,
+            thresholds: vec![ApprovalThresholdResponse {
+                denom: "uusdc".to_string(),
+                threshold: Uint128::new(1000),
+            }],
+        }
+    );
+}
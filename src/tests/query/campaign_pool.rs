@@ -1,22 +1,16 @@
-use std::vec;
-
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
-use cosmwasm_std::{
-    coins, from_binary, from_json, to_json_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg,
-    Deps, DepsMut, Env, MessageInfo, StdError, SubMsg, Uint128,
-};
+use cosmwasm_std::{coins, from_json, to_json_binary, Addr, Env, StdError, Uint128};
+use cw20::Denom;
 
-use crate::contract::{
-    cancel, claim, deposit, instantiate, query_campaign_pool, set_cpool, withdraw,
-};
-use crate::msg::{InstantiateMsg, UserRewardRequest, UserRewardResponse};
-use crate::state::{Campaign, CAMPAIGN_POOL};
+use crate::contract::{deposit, instantiate, query_campaign_pool};
+use crate::msg::{CampaignResponse, InstantiateMsg};
+use crate::state::CampaignStatus;
 
 #[test]
 fn test_query_campaign_pool() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
     let env = mock_env();
 
     instantiate(
@@ -25,6 +19,7 @@ fn test_query_campaign_pool() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -34,21 +29,78 @@ fn test_query_campaign_pool() {
         env.clone(),
         mock_info("sender1", &coins(1000, "")),
         "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
     )
     .unwrap();
 
     let res =
         query_campaign_pool(deps.as_ref(), env.clone(), "test_campaign_1".to_string()).unwrap();
-    let campaign: Campaign = from_json(res).unwrap();
+    let campaign: CampaignResponse = from_json(res).unwrap();
     assert_eq!(
         campaign,
-        Campaign {
+        CampaignResponse {
             amount: Uint128::new(1000),
             owner: Addr::unchecked("sender1"),
+            denom: Denom::Native("".to_string()),
+            start_time: None,
+            end_time: None,
+            merkle_root: None,
+            signer: None,
+            status: CampaignStatus::Active,
         }
     );
 }
 
+#[test]
+fn test_query_campaign_pool_status() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let mut env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let start_time = env.block.time.plus_seconds(100);
+    let end_time = env.block.time.plus_seconds(200);
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender1", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        Some(start_time),
+        Some(end_time),
+        None,
+    )
+    .unwrap();
+
+    let status = |env: &Env| {
+        let res =
+            query_campaign_pool(deps.as_ref(), env.clone(), "test_campaign_1".to_string()).unwrap();
+        let campaign: CampaignResponse = from_json(res).unwrap();
+        assert_eq!(campaign.start_time, Some(start_time));
+        assert_eq!(campaign.end_time, Some(end_time));
+        campaign.status
+    };
+
+    assert_eq!(status(&env), CampaignStatus::Pending);
+
+    env.block.time = start_time;
+    assert_eq!(status(&env), CampaignStatus::Active);
+
+    env.block.time = end_time;
+    assert_eq!(status(&env), CampaignStatus::Ended);
+}
+
 #[test]
 fn test_query_campaign_pool_empty() {
     let mut deps = mock_dependencies();
@@ -60,6 +112,7 @@ fn test_query_campaign_pool_empty() {
         mock_info("creator", &[]),
         InstantiateMsg {
             pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
         },
     )
     .unwrap();
@@ -0,0 +1,57 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, to_json_binary, Uint128};
+
+use crate::contract::{add_denom, instantiate, query_denoms};
+use crate::msg::{DenomResponse, InstantiateMsg, ListDenomsResponse};
+
+#[test]
+fn test_query_denoms() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uusdc".to_string(),
+        Some(Uint128::new(500)),
+    )
+    .unwrap();
+
+    let res = query_denoms(deps.as_ref(), env.clone(), None, None).unwrap();
+    let denoms: ListDenomsResponse = from_json(res).unwrap();
+    assert_eq!(
+        denoms.denoms,
+        vec![
+            DenomResponse {
+                denom: "".to_string(),
+                min_deposit: None,
+            },
+            DenomResponse {
+                denom: "uusdc".to_string(),
+                min_deposit: Some(Uint128::new(500)),
+            },
+        ]
+    );
+
+    let res = query_denoms(deps.as_ref(), env.clone(), Some("".to_string()), Some(1)).unwrap();
+    let denoms: ListDenomsResponse = from_json(res).unwrap();
+    assert_eq!(
+        denoms.denoms,
+        vec![DenomResponse {
+            denom: "uusdc".to_string(),
+            min_deposit: Some(Uint128::new(500)),
+        }]
+    );
+}
@@ -1 +1,9 @@
+mod admin;
+mod approval_policy;
 mod campaign_pool;
+mod denoms;
+mod nonces;
+mod roles;
+mod signer_keys;
+mod signing_domain;
+mod solvency;
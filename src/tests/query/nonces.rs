@@ -0,0 +1,108 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, Addr, Uint128};
+
+use crate::contract::{claim, deposit, instantiate, query_nonces, revoke_nonces};
+use crate::msg::{InstantiateMsg, NonceResponse, NoncesResponse, SignedData};
+use crate::state::NonceStatus;
+use crate::tests::{sign, signer_pubkey};
+
+#[test]
+fn test_query_nonces() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, ""));
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    deposit(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("sender", &coins(1000, "")),
+        "test_campaign_1".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    claim(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        "test_campaign_1".to_string(),
+        "".to_string(),
+        Uint128::new(100),
+        1,
+        None,
+        sign(&SignedData {
+            campaign_id: "test_campaign_1".to_string(),
+            nonce: 1,
+            denom: "".to_string(),
+            amount: Uint128::new(100),
+            sender: Addr::unchecked("user1"),
+            recipient: None,
+            relayer_fee: None,
+            expires_at: None,
+        }),
+        None,
+        None,
+    )
+    .unwrap();
+
+    revoke_nonces(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![2],
+    )
+    .unwrap();
+
+    let res = query_nonces(
+        deps.as_ref(),
+        env.clone(),
+        "test_campaign_1".to_string(),
+        "user1".to_string(),
+        vec![1, 2, 3],
+    )
+    .unwrap();
+    let nonces: NoncesResponse = from_json(res).unwrap();
+    assert_eq!(
+        nonces.nonces,
+        vec![
+            NonceResponse {
+                nonce: 1,
+                status: NonceStatus::Claimed,
+            },
+            NonceResponse {
+                nonce: 2,
+                status: NonceStatus::Revoked,
+            },
+            NonceResponse {
+                nonce: 3,
+                status: NonceStatus::Unused,
+            },
+        ]
+    );
+
+    // nonces are tracked per claimer
+    let res = query_nonces(
+        deps.as_ref(),
+        env.clone(),
+        "test_campaign_1".to_string(),
+        "user2".to_string(),
+        vec![1],
+    )
+    .unwrap();
+    let nonces: NoncesResponse = from_json(res).unwrap();
+    assert_eq!(nonces.nonces[0].status, NonceStatus::Unused);
+}
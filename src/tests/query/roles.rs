@@ -0,0 +1,65 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr};
+
+use crate::contract::{grant_role, instantiate, query_roles};
+use crate::msg::{InstantiateMsg, ListRolesResponse, RoleAssignmentResponse};
+use crate::state::Role;
+use crate::tests::signer_pubkey;
+
+#[test]
+fn test_query_roles() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    for (address, role) in [
+        ("operator1", Role::Treasurer),
+        ("operator1", Role::PoolManager),
+        ("operator2", Role::SignerManager),
+    ] {
+        grant_role(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            address.to_string(),
+            role,
+        )
+        .unwrap();
+    }
+
+    let res = query_roles(deps.as_ref(), env.clone(), None, Some(1)).unwrap();
+    let roles: ListRolesResponse = from_json(res).unwrap();
+    assert_eq!(
+        roles.roles,
+        vec![RoleAssignmentResponse {
+            address: Addr::unchecked("operator1"),
+            roles: vec![Role::Treasurer, Role::PoolManager],
+        }]
+    );
+
+    let res = query_roles(
+        deps.as_ref(),
+        env.clone(),
+        Some("operator1".to_string()),
+        None,
+    )
+    .unwrap();
+    let roles: ListRolesResponse = from_json(res).unwrap();
+    assert_eq!(
+        roles.roles,
+        vec![RoleAssignmentResponse {
+            address: Addr::unchecked("operator2"),
+            roles: vec![Role::SignerManager],
+        }]
+    );
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::from_json;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+use crate::contract::{instantiate, query_signer_keys, rotate_signer};
+use crate::msg::{InstantiateMsg, SignerKeyResponse, SignerKeysResponse};
+use crate::tests::{pubkey_of, signer_pubkey, OTHER_SECRET};
+
+#[test]
+fn test_query_signer_keys() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    rotate_signer(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        pubkey_of(&OTHER_SECRET),
+        Some(60),
+    )
+    .unwrap();
+
+    let res = query_signer_keys(deps.as_ref(), env.clone()).unwrap();
+    let keys: SignerKeysResponse = from_json(res).unwrap();
+    assert_eq!(
+        keys.keys,
+        vec![
+            SignerKeyResponse {
+                pubkey: pubkey_of(&OTHER_SECRET),
+                expires_at: None,
+            },
+            SignerKeyResponse {
+                pubkey: signer_pubkey(),
+                expires_at: Some(env.block.time.plus_seconds(60)),
+            },
+        ]
+    );
+
+    // the old key drops out once the grace period is over
+    let mut later = env.clone();
+    later.block.time = env.block.time.plus_seconds(60);
+    let res = query_signer_keys(deps.as_ref(), later).unwrap();
+    let keys: SignerKeysResponse = from_json(res).unwrap();
+    assert_eq!(keys.keys.len(), 1);
+}
@@ -0,0 +1,44 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, to_json_binary, Addr};
+
+use crate::contract::{instantiate, query_signing_domain, set_legacy_signatures};
+use crate::crypto::SIGNING_VERSION;
+use crate::msg::{InstantiateMsg, SigningDomain, SigningDomainResponse};
+
+#[test]
+fn test_query_signing_domain() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: to_json_binary(&"test_key".to_string()).unwrap(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = query_signing_domain(deps.as_ref(), env.clone()).unwrap();
+    let domain: SigningDomainResponse = from_json(res).unwrap();
+    assert_eq!(
+        domain,
+        SigningDomainResponse {
+            domain: SigningDomain {
+                version: SIGNING_VERSION,
+                chain_id: "cosmos-testnet-14002".to_string(),
+                contract: Addr::unchecked("cosmos2contract"),
+            },
+            legacy_signatures: false,
+            hrp: "archway".to_string(),
+        }
+    );
+
+    set_legacy_signatures(deps.as_mut(), env.clone(), mock_info("creator", &[]), true).unwrap();
+
+    let res = query_signing_domain(deps.as_ref(), env.clone()).unwrap();
+    let domain: SigningDomainResponse = from_json(res).unwrap();
+    assert!(domain.legacy_signatures);
+}
@@ -0,0 +1,121 @@
+use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+use cosmwasm_std::{coin, coins, from_json, Uint128};
+
+use crate::contract::{add_denom, deposit, instantiate, query_solvency};
+use crate::msg::{DenomSolvencyResponse, InstantiateMsg, SolvencyResponse};
+use crate::state::CAMPAIGN_POOL;
+use crate::tests::signer_pubkey;
+
+#[test]
+fn test_query_solvency() {
+    let mut deps = mock_dependencies_with_balance(&[coin(1000, ""), coin(300, "uatom")]);
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            pubkey: signer_pubkey(),
+            hrp: Some("archway".to_string()),
+        },
+    )
+    .unwrap();
+    add_denom(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        "uatom".to_string(),
+        None,
+    )
+    .unwrap();
+
+    for (campaign_id, funds) in [
+        ("test_campaign_1", coins(400, "")),
+        ("test_campaign_2", coins(300, "uatom")),
+        ("test_campaign_3", coins(500, "")),
+    ] {
+        deposit(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("sender", &funds),
+            campaign_id.to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+    deps.querier.update_balance(
+        env.contract.address.clone(),
+        vec![coin(1000, ""), coin(200, "uatom")],
+    );
+
+    let res = query_solvency(deps.as_ref(), env.clone(), None, Some(2)).unwrap();
+    let solvency: SolvencyResponse = from_json(res).unwrap();
+    assert_eq!(
+        solvency,
+        SolvencyResponse {
+            denoms: vec![
+                DenomSolvencyResponse {
+                    denom: "".to_string(),
+                    campaigns: Uint128::new(400),
+                    allocated: Uint128::new(900),
+                    balance: Uint128::new(1000),
+                    surplus: Uint128::new(100),
+                    deficit: Uint128::zero(),
+                },
+                DenomSolvencyResponse {
+                    denom: "uatom".to_string(),
+                    campaigns: Uint128::new(300),
+                    allocated: Uint128::new(300),
+                    balance: Uint128::new(200),
+                    surplus: Uint128::zero(),
+                    deficit: Uint128::new(100),
+                },
+            ],
+            next_start_after: Some("test_campaign_2".to_string()),
+        }
+    );
+
+    let res = query_solvency(
+        deps.as_ref(),
+        env.clone(),
+        solvency.next_start_after,
+        Some(2),
+    )
+    .unwrap();
+    let solvency: SolvencyResponse = from_json(res).unwrap();
+    assert_eq!(solvency.denoms.len(), 1);
+    assert_eq!(solvency.denoms[0].campaigns, Uint128::new(500));
+    assert_eq!(solvency.next_start_after, None);
+
+    // a campaign raised without being allocated still counts towards the deficit
+    let mut campaign = CAMPAIGN_POOL
+        .load(&deps.storage, "test_campaign_3".to_string())
+        .unwrap();
+    campaign.amount = Uint128::new(5000);
+    CAMPAIGN_POOL
+        .save(&mut deps.storage, "test_campaign_3".to_string(), &campaign)
+        .unwrap();
+
+    let res = query_solvency(
+        deps.as_ref(),
+        env.clone(),
+        Some("test_campaign_2".to_string()),
+        None,
+    )
+    .unwrap();
+    let solvency: SolvencyResponse = from_json(res).unwrap();
+    assert_eq!(
+        solvency.denoms,
+        vec![DenomSolvencyResponse {
+            denom: "".to_string(),
+            campaigns: Uint128::new(5000),
+            allocated: Uint128::new(900),
+            balance: Uint128::new(1000),
+            surplus: Uint128::zero(),
+            deficit: Uint128::new(4000),
+        }]
+    );
+}